@@ -0,0 +1,95 @@
+//! Compares the dedicated reductions of `SolinasResidue` with Montgomery arithmetic modulo the same primes
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use crypto_bigint::{
+    impl_modulus,
+    modular::{
+        constant_mod::Residue,
+        solinas_mod::{NistP256, NistP384, SolinasResidue},
+    },
+    U256, U384,
+};
+
+impl_modulus!(
+    P256Modulus,
+    U256,
+    "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+);
+
+impl_modulus!(
+    P384Modulus,
+    U384,
+    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff"
+);
+
+fn bench_p256(c: &mut Criterion) {
+    let mut group = c.benchmark_group("P-256");
+
+    let x = U256::from_be_hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+    let y = U256::from_be_hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");
+    let exponent =
+        U256::from_be_hex("c6bcd0e1d6b2d8b1c1f2e3a4958677a8b9c0d1e2f3041526374859607a8b9cad");
+
+    let (x_sol, y_sol) = (
+        SolinasResidue::<NistP256, { U256::LIMBS }>::new(x),
+        SolinasResidue::<NistP256, { U256::LIMBS }>::new(y),
+    );
+    let (x_mont, y_mont) = (
+        Residue::<P256Modulus, { U256::LIMBS }>::new(x),
+        Residue::<P256Modulus, { U256::LIMBS }>::new(y),
+    );
+
+    group.bench_function("mul, Solinas", |b| {
+        b.iter(|| black_box(x_sol).mul(&black_box(y_sol)))
+    });
+    group.bench_function("mul, Montgomery", |b| {
+        b.iter(|| black_box(x_mont).mul(&black_box(y_mont)))
+    });
+    group.bench_function("pow, Solinas", |b| {
+        b.iter(|| black_box(x_sol).pow(&black_box(exponent)))
+    });
+    group.bench_function("pow, Montgomery", |b| {
+        b.iter(|| black_box(x_mont).pow(&black_box(exponent)))
+    });
+    group.bench_function("inv, Solinas", |b| b.iter(|| black_box(x_sol).inv()));
+    group.bench_function("inv, Montgomery", |b| b.iter(|| black_box(x_mont).inv()));
+
+    group.finish();
+}
+
+fn bench_p384(c: &mut Criterion) {
+    let mut group = c.benchmark_group("P-384");
+
+    let x = U384::from_be_hex("aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7");
+    let y = U384::from_be_hex("3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f");
+    let exponent = U384::from_be_hex("b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef");
+
+    let (x_sol, y_sol) = (
+        SolinasResidue::<NistP384, { U384::LIMBS }>::new(x),
+        SolinasResidue::<NistP384, { U384::LIMBS }>::new(y),
+    );
+    let (x_mont, y_mont) = (
+        Residue::<P384Modulus, { U384::LIMBS }>::new(x),
+        Residue::<P384Modulus, { U384::LIMBS }>::new(y),
+    );
+
+    group.bench_function("mul, Solinas", |b| {
+        b.iter(|| black_box(x_sol).mul(&black_box(y_sol)))
+    });
+    group.bench_function("mul, Montgomery", |b| {
+        b.iter(|| black_box(x_mont).mul(&black_box(y_mont)))
+    });
+    group.bench_function("pow, Solinas", |b| {
+        b.iter(|| black_box(x_sol).pow(&black_box(exponent)))
+    });
+    group.bench_function("pow, Montgomery", |b| {
+        b.iter(|| black_box(x_mont).pow(&black_box(exponent)))
+    });
+    group.bench_function("inv, Solinas", |b| b.iter(|| black_box(x_sol).inv()));
+    group.bench_function("inv, Montgomery", |b| b.iter(|| black_box(x_mont).inv()));
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_p256, bench_p384);
+criterion_main!(benches);
@@ -0,0 +1,81 @@
+//! Values of the Legendre and Jacobi symbols.
+
+use core::ops::Mul;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+/// The value of a Legendre or Jacobi symbol: `-1`, `0` or `1`.
+///
+/// Comparisons are constant-time, so this can be used to make decisions based on secret values.
+#[derive(Copy, Clone, Debug)]
+pub struct JacobiSymbol(i8);
+
+impl JacobiSymbol {
+    /// The symbol value `0`.
+    pub const ZERO: Self = Self(0);
+
+    /// The symbol value `1`.
+    pub const ONE: Self = Self(1);
+
+    /// The symbol value `-1`.
+    pub const MINUS_ONE: Self = Self(-1);
+
+    /// Creates a symbol from a sign bit (`0` for `1`, `1` for `-1`) and a mask which is
+    /// `0xff` if the symbol is non-zero and `0` otherwise.
+    pub(crate) const fn from_sign_and_mask(sign: u8, nonzero: u8) -> Self {
+        Self((1 - 2 * (sign & 1) as i8) & (nonzero as i8))
+    }
+
+    /// Returns the symbol as an `i8`.
+    pub const fn to_i8(self) -> i8 {
+        self.0
+    }
+
+    /// Returns a truthy `Choice` if the symbol is `0`.
+    pub fn is_zero(&self) -> Choice {
+        self.ct_eq(&Self::ZERO)
+    }
+
+    /// Returns a truthy `Choice` if the symbol is `1`.
+    pub fn is_one(&self) -> Choice {
+        self.ct_eq(&Self::ONE)
+    }
+
+    /// Returns a truthy `Choice` if the symbol is `-1`.
+    pub fn is_minus_one(&self) -> Choice {
+        self.ct_eq(&Self::MINUS_ONE)
+    }
+}
+
+impl ConstantTimeEq for JacobiSymbol {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for JacobiSymbol {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(i8::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl Eq for JacobiSymbol {}
+
+impl PartialEq for JacobiSymbol {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Mul for JacobiSymbol {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_mul(rhs.0))
+    }
+}
+
+impl From<JacobiSymbol> for i8 {
+    fn from(symbol: JacobiSymbol) -> i8 {
+        symbol.0
+    }
+}
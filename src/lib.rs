@@ -152,7 +152,7 @@
 //! [`Sub`]: core::ops::Sub
 //! [`CryptoRng`]: rand_core::CryptoRng
 
-#[cfg(all(feature = "alloc", test))]
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
 #[macro_use]
@@ -161,6 +161,7 @@ mod nlimbs;
 #[cfg(feature = "generic-array")]
 mod array;
 mod checked;
+mod jacobi;
 mod limb;
 mod non_zero;
 mod traits;
@@ -169,6 +170,7 @@ mod wrapping;
 
 pub use crate::{
     checked::Checked,
+    jacobi::JacobiSymbol,
     limb::{Limb, WideWord, Word},
     non_zero::NonZero,
     traits::*,
@@ -189,6 +191,9 @@ pub use {
     generic_array::{self, typenum::consts},
 };
 
+#[cfg(feature = "ff")]
+pub use ff;
+
 #[cfg(feature = "rand_core")]
 pub use rand_core;
 
@@ -23,6 +23,7 @@ mod div;
 mod encoding;
 mod from;
 mod inv_mod;
+mod jacobi;
 mod mul;
 mod mul_mod;
 mod neg;
@@ -0,0 +1,85 @@
+//! [`UInt`] Jacobi symbol computation.
+
+use crate::{JacobiSymbol, UInt, Word};
+
+impl<const LIMBS: usize> UInt<LIMBS> {
+    /// Computes the Jacobi symbol `(self / n)` in constant time.
+    ///
+    /// Uses the binary Jacobi algorithm with a fixed number of iterations,
+    /// mirroring the structure of [`UInt::inv_odd_mod`].
+    ///
+    /// `n` must be odd, otherwise the result is meaningless.
+    pub const fn jacobi(&self, n: &Self) -> JacobiSymbol {
+        debug_assert!(n.ct_is_odd() == Word::MAX);
+
+        let mut a = *self;
+        let mut b = *n;
+
+        // The sign of the result, where 0 represents `1` and 1 represents `-1`
+        let mut sign: Word = 0;
+
+        let mut i = 0;
+        while i < 2 * LIMBS * Word::BITS as usize {
+            let a_odd = a.ct_is_odd();
+
+            // Set `a -= b` if `a` is odd.
+            let (new_a, swap) = a.conditional_wrapping_sub(&b, a_odd);
+            // If `a < b`, swap them (using quadratic reciprocity): `b = a` and `a = b - a`.
+            // The sign flips if both were 3 mod 4.
+            sign ^= swap & (a.limbs[0].0 & b.limbs[0].0) >> 1 & 1;
+            b = UInt::ct_select(b, b.wrapping_add(&new_a), swap);
+            a = new_a.conditional_wrapping_neg(swap);
+
+            // `a` is now even: divide it by two, which flips the sign if `b` is 3 or 5 mod 8.
+            let b_lo = b.limbs[0].0;
+            sign ^= ((b_lo >> 1) ^ (b_lo >> 2)) & 1 & a.ct_is_nonzero();
+            a = a.shr_1().0;
+
+            i += 1;
+        }
+
+        debug_assert!(a.ct_is_nonzero() == 0);
+
+        // `b` is now `gcd(self, n)`, so the symbol is zero unless `b == 1`.
+        let coprime = b.ct_not_eq(&UInt::ONE) ^ Word::MAX;
+        JacobiSymbol::from_sign_and_mask(sign as u8, coprime as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{JacobiSymbol, U256};
+
+    #[test]
+    fn jacobi_small() {
+        let n = U256::from(45u64);
+        let expected = [
+            0, 1, -1, 0, 1, 0, 0, -1, -1, 0, 0, 1, 0, -1, 1, 0, 1, -1, 0, 1, 0, 0, -1, -1, 0,
+        ];
+
+        for (a, expected) in expected.iter().enumerate() {
+            let a = U256::from(a as u64);
+            assert_eq!(i8::from(a.jacobi(&n)), *expected, "({} / 45)", a);
+        }
+    }
+
+    #[test]
+    fn jacobi_one() {
+        let a = U256::from(12345u64);
+        assert_eq!(a.jacobi(&U256::ONE), JacobiSymbol::ONE);
+    }
+
+    #[test]
+    fn jacobi_large() {
+        let n =
+            U256::from_be_hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+        let a =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+        let b =
+            U256::from_be_hex("d5777c45019673125ad240f83094d4252d829516fac8601ed01979ec1ec1a251");
+
+        assert_eq!(a.jacobi(&n), JacobiSymbol::ONE);
+        assert_eq!(b.jacobi(&n), JacobiSymbol::MINUS_ONE);
+        assert_eq!(n.jacobi(&n), JacobiSymbol::ZERO);
+    }
+}
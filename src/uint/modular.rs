@@ -4,15 +4,84 @@ use crate::{UInt, Word};
 
 mod reduction;
 
+/// Implements the binary operator `$trait` for all combinations of owned and borrowed residues in terms of `$function(&lhs, &rhs)`, as well as the `$assign_trait` variants if given.
+///
+/// This is exported for use by `impl_prime_field!`, and is not part of the public API.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_residue_binary_op {
+    ([$($generics:tt)*] $residue:ty, $output:ty, $trait:ident, $method:ident, $function:path) => {
+        impl<$($generics)*> core::ops::$trait<&$residue> for &$residue {
+            type Output = $output;
+
+            fn $method(self, rhs: &$residue) -> $output {
+                $function(self, rhs)
+            }
+        }
+
+        impl<$($generics)*> core::ops::$trait<$residue> for &$residue {
+            type Output = $output;
+
+            fn $method(self, rhs: $residue) -> $output {
+                $function(self, &rhs)
+            }
+        }
+
+        impl<$($generics)*> core::ops::$trait<&$residue> for $residue {
+            type Output = $output;
+
+            fn $method(self, rhs: &$residue) -> $output {
+                $function(&self, rhs)
+            }
+        }
+
+        impl<$($generics)*> core::ops::$trait<$residue> for $residue {
+            type Output = $output;
+
+            fn $method(self, rhs: $residue) -> $output {
+                $function(&self, &rhs)
+            }
+        }
+    };
+    ([$($generics:tt)*] $residue:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $function:path) => {
+        $crate::impl_residue_binary_op!([$($generics)*] $residue, $residue, $trait, $method, $function);
+
+        impl<$($generics)*> core::ops::$assign_trait<&$residue> for $residue {
+            fn $assign_method(&mut self, rhs: &$residue) {
+                *self = $function(self, rhs);
+            }
+        }
+
+        impl<$($generics)*> core::ops::$assign_trait<$residue> for $residue {
+            fn $assign_method(&mut self, rhs: $residue) {
+                *self = $function(self, &rhs);
+            }
+        }
+    };
+}
+
+/// Implements a lazily reduced accumulator for sums of products of residues.
+pub mod accumulator;
+/// Implements Barrett reduction, supporting modular multiplication of plain integers with a modulus set at runtime.
+pub mod barrett;
 /// Implements `Residue`s, supporting modular arithmetic with a constant modulus.
 pub mod constant_mod;
+/// Implements `AnyDynResidue`s, supporting modular arithmetic with any non-zero modulus set at runtime.
+pub mod runtime_any_mod;
 /// Implements `DynResidue`s, supporting modular arithmetic with a modulus set at runtime.
 pub mod runtime_mod;
+/// Implements `SolinasResidue`s, supporting modular arithmetic with dedicated reductions for primes such as the NIST primes.
+pub mod solinas_mod;
+/// Implements `PseudoMersenneResidue`s, supporting modular arithmetic with a pseudo-Mersenne modulus.
+pub mod special_mod;
 
 mod add;
 mod inv;
 mod mul;
+mod neg;
 mod pow;
+mod sqrt;
+mod sub;
 
 /// Provides a consistent interface to add two residues of the same type together.
 pub trait AddResidue {
@@ -20,6 +89,18 @@ pub trait AddResidue {
     fn add(&self, rhs: &Self) -> Self;
 }
 
+/// Provides a consistent interface to subtract two residues of the same type.
+pub trait SubResidue {
+    /// Computes the (reduced) difference of two residues.
+    fn sub(&self, rhs: &Self) -> Self;
+}
+
+/// Provides a consistent interface to negate a residue.
+pub trait NegResidue {
+    /// Computes the (reduced) additive inverse of a residue.
+    fn neg(&self) -> Self;
+}
+
 /// Provides a consistent interface to multiply two residues of the same type together.
 pub trait MulResidue
 where
@@ -39,13 +120,17 @@ pub trait PowResidue<const LIMBS: usize>
 where
     Self: Sized,
 {
-    /// Computes the (reduced) exponentiation of a residue.
-    fn pow(self, exponent: &UInt<LIMBS>) -> Self {
-        self.pow_specific(exponent, LIMBS * Word::BITS as usize)
+    /// Computes the (reduced) exponentiation of a residue. The exponent may have a different number of limbs than the residue.
+    fn pow<const RHS_LIMBS: usize>(self, exponent: &UInt<RHS_LIMBS>) -> Self {
+        self.pow_specific(exponent, RHS_LIMBS * Word::BITS as usize)
     }
 
     /// Computes the (reduced) exponentiation of a residue, here `exponent_bits` represents the number of bits to take into account for the exponent. Note that this value is leaked in the time pattern.
-    fn pow_specific(self, exponent: &UInt<LIMBS>, exponent_bits: usize) -> Self;
+    fn pow_specific<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self;
 }
 
 /// Provides a consistent interface to invert a residue.
@@ -57,9 +142,20 @@ where
     fn inv(self) -> CtOption<Self>;
 }
 
+/// Provides a consistent interface to compute the square root of a residue.
+pub trait SqrtResidue
+where
+    Self: Sized,
+{
+    /// Computes a square root of the residue, assuming the modulus is an odd prime. Returns CtOption, which is `None` if the residue is not a quadratic residue.
+    ///
+    /// Which of the two square roots is returned is unspecified.
+    fn sqrt(&self) -> CtOption<Self>;
+}
+
 /// The `GenericResidue` trait provides a consistent API for dealing with residues with a constant modulus.
 pub trait GenericResidue<const LIMBS: usize>:
-    AddResidue + MulResidue + PowResidue<LIMBS> + InvResidue
+    AddResidue + SubResidue + NegResidue + MulResidue + PowResidue<LIMBS> + InvResidue
 {
     /// Retrieves the integer currently encoded in this `Residue`, guaranteed to be reduced.
     fn retrieve(&self) -> UInt<LIMBS>;
@@ -70,10 +166,12 @@ mod tests {
     use crate::{
         const_residue, impl_modulus,
         modular::{
-            constant_mod::Residue, constant_mod::ResidueParams, reduction::montgomery_reduction,
+            constant_mod::Residue,
+            constant_mod::ResidueParams,
+            reduction::montgomery_reduction,
+            runtime_mod::{DynResidue, DynResidueParams},
         },
-        traits::Encoding,
-        UInt, U256, U64,
+        JacobiSymbol, UInt, U256, U64,
     };
 
     impl_modulus!(
@@ -203,4 +301,290 @@ mod tests {
             const_residue!(x, Modulus2)
         );
     }
+
+    #[test]
+    fn test_legendre() {
+        let x =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+        let y =
+            U256::from_be_hex("d5777c45019673125ad240f83094d4252d829516fac8601ed01979ec1ec1a251");
+        let zero = U256::ZERO;
+
+        assert_eq!(const_residue!(x, Modulus2).legendre(), JacobiSymbol::ONE);
+        assert_eq!(
+            const_residue!(y, Modulus2).legendre(),
+            JacobiSymbol::MINUS_ONE
+        );
+        assert_eq!(
+            const_residue!(zero, Modulus2).legendre(),
+            JacobiSymbol::ZERO
+        );
+
+        let params = DynResidueParams::new(Modulus2::MODULUS);
+        assert_eq!(DynResidue::new(x, params).legendre(), JacobiSymbol::ONE);
+        assert_eq!(
+            DynResidue::new(y, params).legendre(),
+            JacobiSymbol::MINUS_ONE
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        let x =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+
+        let params = DynResidueParams::from_params::<Modulus2>();
+        assert_eq!(params, DynResidueParams::new(Modulus2::MODULUS));
+
+        let residue = const_residue!(x, Modulus2);
+        let dyn_residue = DynResidue::from(residue);
+        assert_eq!(dyn_residue, DynResidue::new(x, params));
+        assert_eq!(
+            Residue::<Modulus2, { Modulus2::LIMBS }>::try_from_dyn(&dyn_residue),
+            Some(residue)
+        );
+        assert_eq!(
+            Residue::<Modulus1, { Modulus1::LIMBS }>::try_from_dyn(&dyn_residue),
+            None
+        );
+    }
+
+    #[test]
+    fn test_montgomery_form_access() {
+        let x =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+
+        let residue = const_residue!(x, Modulus2);
+        let montgomery_form = *residue.as_montgomery();
+        assert_eq!(
+            montgomery_form,
+            UInt::ct_reduce_wide(x.mul_wide(&Modulus2::R), &Modulus2::MODULUS).0
+        );
+        assert_eq!(
+            Residue::<Modulus2, { Modulus2::LIMBS }>::from_montgomery(montgomery_form),
+            residue
+        );
+
+        let params = DynResidueParams::new(Modulus2::MODULUS);
+        assert_eq!(params.modulus(), &Modulus2::MODULUS);
+        assert_eq!(params.r(), &Modulus2::R);
+        assert_eq!(params.r2(), &Modulus2::R2);
+        assert_eq!(params.mod_neg_inv(), Modulus2::MOD_NEG_INV);
+
+        let dyn_residue = DynResidue::new(x, params);
+        assert_eq!(dyn_residue.as_montgomery(), &montgomery_form);
+        assert_eq!(
+            DynResidue::from_montgomery(montgomery_form, params),
+            dyn_residue
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn test_zeroize() {
+        use zeroize::Zeroize;
+
+        let x =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+
+        let mut residue = const_residue!(x, Modulus2);
+        residue.zeroize();
+        assert_eq!(residue.as_montgomery(), &U256::ZERO);
+
+        let mut dyn_residue = DynResidue::new(x, DynResidueParams::new(Modulus2::MODULUS));
+        dyn_residue.zeroize();
+        assert_eq!(dyn_residue.as_montgomery(), &U256::ZERO);
+        assert_eq!(dyn_residue.params().modulus(), &U256::ZERO);
+        assert_eq!(dyn_residue.params().r2(), &U256::ZERO);
+    }
+
+    #[test]
+    fn test_constant_time_traits() {
+        use subtle::{ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq};
+
+        let x =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+
+        let residue = const_residue!(x, Modulus2);
+        let zero = Residue::<Modulus2, { Modulus2::LIMBS }>::ZERO;
+        let one = Residue::<Modulus2, { Modulus2::LIMBS }>::ONE;
+        assert_eq!(zero.retrieve(), U256::ZERO);
+        assert!(bool::from(residue.ct_eq(&residue)));
+        assert!(!bool::from(residue.ct_eq(&one)));
+        assert_eq!(Residue::conditional_select(&zero, &one, 1.into()), one);
+        let mut negated = residue;
+        negated.conditional_negate(0.into());
+        assert_eq!(negated, residue);
+        negated.conditional_negate(1.into());
+        assert_eq!(negated, -residue);
+
+        let params = DynResidueParams::new(Modulus2::MODULUS);
+        let other_params = DynResidueParams::new(Modulus1::MODULUS);
+        let dyn_residue = DynResidue::new(x, params);
+        let zero = DynResidue::zero(params);
+        let one = DynResidue::one(params);
+        assert_eq!(zero.retrieve(), U256::ZERO);
+        assert_eq!(one.retrieve(), U256::ONE);
+        assert!(bool::from(dyn_residue.ct_eq(&dyn_residue)));
+        assert!(!bool::from(dyn_residue.ct_eq(&one)));
+        assert!(!bool::from(one.ct_eq(&DynResidue::one(other_params))));
+        assert_eq!(
+            DynResidue::conditional_select(&zero, &DynResidue::one(other_params), 1.into()),
+            DynResidue::one(other_params)
+        );
+        let mut negated = dyn_residue;
+        negated.conditional_negate(0.into());
+        assert_eq!(negated, dyn_residue);
+        negated.conditional_negate(1.into());
+        assert_eq!(negated, -dyn_residue);
+    }
+
+    impl_modulus!(
+        Modulus3,
+        U256,
+        "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+        7
+    );
+
+    impl_modulus!(
+        Modulus4,
+        U256,
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+    );
+
+    #[test]
+    fn test_derived_constants() {
+        assert_eq!(
+            Modulus1::MODULUS_MINUS_ONE_HALF,
+            U256::from_be_hex("39f6d3a994cebea4199cec0404d0ec02a9ded2017fff2dff7fffffff80000000")
+        );
+        assert_eq!(Modulus1::TWO_ADICITY, 32);
+        assert_eq!(
+            Modulus1::ODD_PART,
+            U256::from_be_hex("0000000073eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff")
+        );
+        assert_eq!(Modulus1::MULTIPLICATIVE_GENERATOR, None);
+        assert_eq!(Modulus1::QUADRATIC_NON_RESIDUE, U256::from(5u64));
+        assert_eq!(
+            Modulus1::ROOT_OF_UNITY,
+            U256::from_be_hex("0212d79e5b416b6f0fd56dc8d168d6c0c4024ff270b3e0941b788f500b912f1f")
+        );
+
+        assert_eq!(Modulus3::MULTIPLICATIVE_GENERATOR, Some(U256::from(7u64)));
+        assert_eq!(Modulus3::QUADRATIC_NON_RESIDUE, U256::from(7u64));
+        assert_eq!(
+            Modulus3::ROOT_OF_UNITY,
+            U256::from_be_hex("16a2a19edfe81f20d09b681922c813b4b63683508c2280b93829971f439f0d2b")
+        );
+
+        assert_eq!(Modulus4::TWO_ADICITY, 1);
+        assert_eq!(
+            Modulus4::MODULUS_PLUS_ONE_QUARTER,
+            U256::from_be_hex("3fffffffc0000000400000000000000000000000400000000000000000000000")
+        );
+        assert_eq!(Modulus4::QUADRATIC_NON_RESIDUE, U256::from(3u64));
+        assert_eq!(
+            Modulus4::ROOT_OF_UNITY,
+            Modulus4::MODULUS.wrapping_sub(&U256::ONE)
+        );
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_operators() {
+        let x =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+        let y =
+            U256::from_be_hex("d5777c45019673125ad240f83094d4252d829516fac8601ed01979ec1ec1a251");
+
+        let a = const_residue!(x, Modulus2);
+        let b = const_residue!(y, Modulus2);
+        let product = a * b;
+        assert_eq!(&a * &b, product);
+        assert_eq!(a * &b, product);
+        assert_eq!(&a * b, product);
+        assert_eq!(a + b - b, a);
+        assert_eq!(&a + &b - &b, a);
+        assert_eq!((product / b).unwrap(), a);
+        assert_eq!((&product / &b).unwrap(), a);
+        assert!(bool::from((a / Residue::ZERO).is_none()));
+        let mut c = a;
+        c *= b;
+        c -= &a;
+        c += a;
+        assert_eq!(c, product);
+        assert_eq!(
+            [a, b]
+                .iter()
+                .sum::<Residue<Modulus2, { Modulus2::LIMBS }>>(),
+            a + b
+        );
+        assert_eq!(
+            [a, b]
+                .into_iter()
+                .product::<Residue<Modulus2, { Modulus2::LIMBS }>>(),
+            product
+        );
+        assert_eq!([a, b].into_iter().sum::<Residue<_, _>>(), a + b);
+        assert_eq!([a, b].iter().product::<Residue<_, _>>(), product);
+        let empty: [Residue<Modulus2, { Modulus2::LIMBS }>; 0] = [];
+        assert_eq!(empty.iter().sum::<Residue<_, _>>(), Residue::ZERO);
+        assert_eq!(empty.into_iter().product::<Residue<_, _>>(), Residue::ONE);
+
+        let params = DynResidueParams::new(Modulus2::MODULUS);
+        let a = DynResidue::new(x, params);
+        let b = DynResidue::new(y, params);
+        let product = a * b;
+        assert_eq!(
+            product.retrieve(),
+            (const_residue!(x, Modulus2) * const_residue!(y, Modulus2)).retrieve()
+        );
+        assert_eq!(&a * &b, product);
+        assert_eq!(a * &b, product);
+        assert_eq!(&a * b, product);
+        assert_eq!(a + b - b, a);
+        assert_eq!(&a + &b - &b, a);
+        assert_eq!((product / b).unwrap(), a);
+        assert_eq!((&product / &b).unwrap(), a);
+        assert!(bool::from((a / DynResidue::zero(params)).is_none()));
+        let mut c = a;
+        c *= &b;
+        c -= a;
+        c += &a;
+        assert_eq!(c, product);
+        assert_eq!(DynResidue::sum([a, b].iter(), &params), a + b);
+        assert_eq!(DynResidue::product([a, b], &params), product);
+        let empty: [DynResidue<{ U256::LIMBS }>; 0] = [];
+        assert_eq!(DynResidue::sum(empty, &params), DynResidue::zero(params));
+        assert_eq!(
+            DynResidue::product(empty.iter(), &params),
+            DynResidue::one(params)
+        );
+    }
+
+    #[test]
+    fn test_sum_of_products() {
+        let values = [
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56"),
+            U256::from_be_hex("d5777c45019673125ad240f83094d4252d829516fac8601ed01979ec1ec1a251"),
+            U256::from_be_hex("1a2472fde50286541d97ca6a3592dd75beb9c9646e40c511b82496cfc3926956"),
+        ];
+
+        let a = values.map(|x| const_residue!(x, Modulus2));
+        let b = [a[2], a[0], a[1]];
+        let expected = a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+        assert_eq!(Residue::sum_of_products(&a, &b), expected);
+
+        let params = DynResidueParams::new(Modulus2::MODULUS);
+        let a = values.map(|x| DynResidue::new(x, params));
+        let b = [a[2], a[0], a[1]];
+        assert_eq!(
+            DynResidue::sum_of_products(&a, &b, &params).retrieve(),
+            expected.retrieve()
+        );
+        assert_eq!(
+            DynResidue::sum_of_products(&[], &[], &params),
+            DynResidue::zero(params)
+        );
+    }
 }
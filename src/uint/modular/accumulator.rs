@@ -0,0 +1,76 @@
+use crate::{Limb, UInt};
+
+use super::reduction::montgomery_reduction;
+
+/// An unreduced double-width accumulator for sums of products of Montgomery forms, such as inner products.
+///
+/// Each product is added in double width, and only the upper half is kept below the modulus, using a single conditional subtraction. The sum is reduced once at the end with a single Montgomery reduction, instead of once per product.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WideAccumulator<const LIMBS: usize> {
+    lower: UInt<LIMBS>,
+    // Always less than the modulus, so that the accumulated value is less than `modulus * R`
+    upper: UInt<LIMBS>,
+    modulus: UInt<LIMBS>,
+    mod_neg_inv: Limb,
+}
+
+impl<const LIMBS: usize> WideAccumulator<LIMBS> {
+    /// Creates an empty accumulator for the given odd `modulus`, where `mod_neg_inv` is the lowest limb of `-(modulus^-1) mod R`.
+    pub const fn new(modulus: UInt<LIMBS>, mod_neg_inv: Limb) -> Self {
+        Self {
+            lower: UInt::ZERO,
+            upper: UInt::ZERO,
+            modulus,
+            mod_neg_inv,
+        }
+    }
+
+    /// Adds the product `a * b` without reducing it. Both operands must be less than the modulus.
+    pub fn add_product(&mut self, a: &UInt<LIMBS>, b: &UInt<LIMBS>) {
+        let (lower, upper) = a.mul_wide(b);
+        let (lower, carry) = self.lower.adc(&lower, Limb::ZERO);
+        let (upper, carry) = self.upper.adc(&upper, carry);
+
+        // The sum is less than `2 * modulus * R`, so subtracting `modulus * R` once if the upper half
+        // is at least the modulus brings it back below `modulus * R` without changing its residue.
+        let (reduced, borrow) = upper.sbb(&self.modulus, Limb::ZERO);
+        let subtract = carry.0.wrapping_neg() | !borrow.0;
+
+        self.lower = lower;
+        self.upper = UInt::ct_select(upper, reduced, subtract);
+    }
+
+    /// Returns the Montgomery reduction of the accumulated value, i.e. the sum of the products times `R^-1 mod modulus`.
+    ///
+    /// If all the operands were Montgomery forms, this is the Montgomery form of the sum of the products.
+    pub const fn reduce(&self) -> UInt<LIMBS> {
+        montgomery_reduction((self.lower, self.upper), self.modulus, self.mod_neg_inv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WideAccumulator;
+    use crate::{
+        modular::runtime_mod::{DynResidue, DynResidueParams},
+        U256,
+    };
+
+    #[test]
+    fn many_products() {
+        // A modulus close to `R` maximizes the size of the products
+        let params = DynResidueParams::new(U256::from_be_hex(
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff43",
+        ));
+        let x = -DynResidue::new(U256::MAX, params);
+
+        let mut accumulator = WideAccumulator::new(*params.modulus(), params.mod_neg_inv());
+        let mut expected = DynResidue::zero(params);
+        for _ in 0..100 {
+            accumulator.add_product(x.as_montgomery(), x.as_montgomery());
+            expected += x * x;
+        }
+
+        assert_eq!(accumulator.reduce(), *expected.as_montgomery());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{Limb, UInt, Word};
+
+/// Precomputed parameters for Barrett reduction modulo a non-zero modulus set at runtime.
+///
+/// Unlike [`DynResidue`](super::runtime_mod::DynResidue), values are kept as plain integers, so there is no cost for converting to and from Montgomery form. This makes it the better choice for a handful of modular multiplications with the same modulus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrettReducer<const LIMBS: usize> {
+    modulus: UInt<LIMBS>,
+    // The bit length `k` of the modulus
+    k: usize,
+    // floor((2^(2k) - 1) / modulus) - 2^k, which always fits in `k` bits
+    mu: UInt<LIMBS>,
+}
+
+impl<const LIMBS: usize> BarrettReducer<LIMBS> {
+    /// Precomputes the reciprocal of `modulus`.
+    ///
+    /// Panics if `modulus` is zero. This is variable-time with respect to `modulus`.
+    pub const fn new(modulus: UInt<LIMBS>) -> Self {
+        assert!(
+            modulus.ct_is_nonzero() == Word::MAX,
+            "modulus must be non-zero"
+        );
+
+        let k = modulus.bits_vartime();
+
+        // Long division of 2^(2k) - 1 (all ones) by the modulus, one bit at a time.
+        // The quotient has exactly k + 1 bits, and its top bit is dropped.
+        let mut quotient = UInt::ZERO;
+        let mut remainder = UInt::ZERO;
+        let mut i = 0;
+        while i < 2 * k {
+            let (shifted, carry) = remainder.shl_1();
+            remainder = shifted.bitor(&UInt::ONE);
+            let (reduced, borrow) = remainder.sbb(&modulus, Limb::ZERO);
+            // Subtract if the shift overflowed, or if no borrow occurred
+            let subtract = carry | !borrow.0;
+            remainder = UInt::ct_select(remainder, reduced, subtract);
+            quotient = quotient.shl_1().0.bitor(&UInt::from_word(subtract & 1));
+            i += 1;
+        }
+
+        let mu = if k < LIMBS * Limb::BIT_SIZE {
+            quotient.bitand(&UInt::ONE.shl_vartime(k).wrapping_sub(&UInt::ONE))
+        } else {
+            quotient
+        };
+
+        Self { modulus, k, mu }
+    }
+
+    /// Returns the modulus.
+    pub const fn modulus(&self) -> &UInt<LIMBS> {
+        &self.modulus
+    }
+
+    /// Computes `x mod modulus`, where `x = lower_upper.0 + lower_upper.1 * 2^(LIMBS * Word::BITS)`.
+    ///
+    /// `x` must be less than `2^(2k)`, where `k` is the bit length of the modulus, which holds in particular for the product of two reduced values.
+    pub const fn reduce_wide(&self, lower_upper: (UInt<LIMBS>, UInt<LIMBS>)) -> UInt<LIMBS> {
+        let k = self.k;
+
+        // q = ((x >> k) * (2^k + mu)) >> k underestimates x / modulus by at most 3
+        let x_shifted = UInt::shr_vartime_wide(lower_upper, k).0;
+        let (lo, hi) = x_shifted.mul_wide(&self.mu);
+        let (x_lo, x_hi) = UInt::shl_vartime_wide((x_shifted, UInt::ZERO), k);
+        let (lo, carry) = lo.adc(&x_lo, Limb::ZERO);
+        let (hi, carry) = hi.adc(&x_hi, carry);
+        let q_lo = UInt::shr_vartime_wide((lo, hi), k).0;
+        // The quotient only overflows a single `UInt` if `k` is the full width
+        let q_hi = carry;
+
+        // r = x - q * modulus < 4 * modulus, computed in double width
+        let (qm_lo, qm_hi) = q_lo.mul_wide(&self.modulus);
+        let qm_hi = qm_hi.wrapping_add(&UInt::ct_select(
+            UInt::ZERO,
+            self.modulus,
+            q_hi.0.wrapping_neg(),
+        ));
+        let (mut r_lo, borrow) = lower_upper.0.sbb(&qm_lo, Limb::ZERO);
+        let (mut r_hi, _) = lower_upper.1.sbb(&qm_hi, borrow);
+
+        let mut i = 0;
+        while i < 3 {
+            let (lo, borrow) = r_lo.sbb(&self.modulus, Limb::ZERO);
+            let (hi, borrow) = r_hi.sbb(&UInt::ZERO, borrow);
+            r_lo = UInt::ct_select(lo, r_lo, borrow.0);
+            r_hi = UInt::ct_select(hi, r_hi, borrow.0);
+            i += 1;
+        }
+
+        r_lo
+    }
+
+    /// Computes `lhs * rhs mod modulus`. Both operands must be reduced.
+    pub const fn mul_mod(&self, lhs: &UInt<LIMBS>, rhs: &UInt<LIMBS>) -> UInt<LIMBS> {
+        self.reduce_wide(lhs.mul_wide(rhs))
+    }
+
+    /// Computes `x^2 mod modulus`. `x` must be reduced.
+    pub const fn square_mod(&self, x: &UInt<LIMBS>) -> UInt<LIMBS> {
+        self.reduce_wide(x.square_wide())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BarrettReducer;
+    use crate::{NonZero, UInt, U128, U256};
+
+    #[test]
+    fn mul_mod() {
+        let modulus =
+            U256::from_be_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+        let reducer = BarrettReducer::new(modulus);
+
+        let x =
+            U256::from_be_hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+        let y =
+            U256::from_be_hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");
+        assert_eq!(
+            reducer.mul_mod(&x, &y),
+            U256::from_be_hex("823cd15f6dd3c71933565064513a6b2bd183e554c6a08622f713ebbbface98be")
+        );
+        assert_eq!(reducer.square_mod(&x), reducer.mul_mod(&x, &x));
+    }
+
+    #[test]
+    fn matches_division() {
+        let moduli = [
+            U128::ONE,
+            U128::from(2u64),
+            U128::from(0xffff_fffbu64),
+            U128::ONE.shl_vartime(127),
+            U128::MAX,
+            U128::from_be_hex("0000000000000001ffffffffffffffff"),
+        ];
+        let values = [
+            U128::ZERO,
+            U128::ONE,
+            U128::from_be_hex("0123456789abcdeffedcba9876543210"),
+            U128::MAX,
+        ];
+
+        for modulus in moduli {
+            let reducer = BarrettReducer::new(modulus);
+            let nz = NonZero::new(modulus).unwrap();
+            for x in values {
+                let x = x % nz;
+                for y in values {
+                    let y = y % nz;
+                    let (lo, hi) = x.mul_wide(&y);
+                    let expected = UInt::ct_reduce_wide((lo, hi), &modulus).0;
+                    assert_eq!(reducer.mul_mod(&x, &y), expected);
+                }
+            }
+        }
+    }
+}
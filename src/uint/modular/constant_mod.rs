@@ -1,19 +1,43 @@
 use core::marker::PhantomData;
 
-use subtle::{Choice, ConditionallySelectable};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 
-use crate::{Limb, UInt};
+use crate::{JacobiSymbol, Limb, UInt, Word};
 
-use super::{reduction::montgomery_reduction, GenericResidue};
+use super::{
+    pow::pow_montgomery_form_vartime,
+    reduction::montgomery_reduction,
+    runtime_mod::DynResidue,
+    sqrt::{find_non_residue, two_adicity},
+    GenericResidue,
+};
 
 /// Additions between residues with a constant modulus
 mod const_add;
+/// Encodings of residues with a constant modulus
+#[cfg(any(feature = "serde", all(feature = "der", feature = "generic-array")))]
+mod const_encoding;
 /// Multiplicative inverses of residues with a constant modulus
 mod const_inv;
 /// Multiplications between residues with a constant modulus
 mod const_mul;
+/// Negations of residues with a constant modulus
+mod const_neg;
+/// Polynomials over residues with a constant modulus
+mod const_poly;
 /// Exponentiation of residues with a constant modulus
 mod const_pow;
+/// Random residues with a constant modulus
+#[cfg(feature = "rand_core")]
+mod const_rand;
+/// Square roots of residues with a constant modulus
+mod const_sqrt;
+/// Subtractions between residues with a constant modulus
+mod const_sub;
+
+#[cfg(feature = "alloc")]
+pub use self::const_poly::VecPolynomial;
+pub use self::{const_poly::Polynomial, const_pow::FixedBaseTable};
 
 #[macro_use]
 /// Macros to remove the boilerplate code when dealing with constant moduli.
@@ -21,6 +45,8 @@ pub mod macros;
 
 /// The parameters to efficiently go to and from the Montgomery form for a given odd modulus. An easy way to generate these parameters is using the `impl_modulus!` macro. These parameters are constant, so they cannot be set at runtime.
 ///
+/// Only `LIMBS` and `MODULUS` have to be given: the Montgomery constants default to their values derived from `MODULUS`, which are computed at compile time.
+///
 /// Unfortunately, `LIMBS` must be generic for now until const generics are stabilized.
 pub trait ResidueParams<const LIMBS: usize>: Copy {
     /// Number of limbs required to encode a residue
@@ -28,15 +54,66 @@ pub trait ResidueParams<const LIMBS: usize>: Copy {
 
     /// The constant modulus
     const MODULUS: UInt<LIMBS>;
-    /// Parameter used in Montgomery reduction
-    const R: UInt<LIMBS>;
+    /// Parameter used in Montgomery reduction: `R = 2^(LIMBS * Word::BITS) mod MODULUS`, the Montgomery form of 1
+    const R: UInt<LIMBS> = UInt::MAX
+        .ct_reduce(&Self::MODULUS)
+        .0
+        .wrapping_add(&UInt::ONE);
     /// R^2, used to move into Montgomery form
-    const R2: UInt<LIMBS>;
+    const R2: UInt<LIMBS> = UInt::ct_reduce_wide(Self::R.square_wide(), &Self::MODULUS).0;
     /// R^3, used to perform a multiplicative inverse
-    const R3: UInt<LIMBS>;
+    const R3: UInt<LIMBS> =
+        montgomery_reduction(Self::R2.square_wide(), Self::MODULUS, Self::MOD_NEG_INV);
     /// The lowest limbs of -(MODULUS^-1) mod R
     // We only need the LSB because during reduction this value is multiplied modulo 2**64.
-    const MOD_NEG_INV: Limb;
+    const MOD_NEG_INV: Limb =
+        Limb(Word::MIN.wrapping_sub(Self::MODULUS.inv_mod2k(Word::BITS as usize).limbs[0].0));
+
+    // The constants below are derived from the ones above, and are only evaluated when used.
+    // Those involving non-residues and roots of unity assume that `MODULUS` is prime.
+
+    /// `(MODULUS - 1) / 2`, the exponent of Euler's criterion
+    const MODULUS_MINUS_ONE_HALF: UInt<LIMBS> = Self::MODULUS.shr_vartime(1);
+    /// `(MODULUS + 1) / 4` rounded down, the exponent of square roots when `MODULUS = 3 mod 4`
+    const MODULUS_PLUS_ONE_QUARTER: UInt<LIMBS> = Self::MODULUS
+        .shr_vartime(1)
+        .wrapping_add(&UInt::ONE)
+        .shr_vartime(1);
+    /// The 2-adicity `s` of `MODULUS - 1`, i.e. the largest `s` such that `2^s` divides it
+    const TWO_ADICITY: usize = two_adicity(&Self::MODULUS);
+    /// The odd part `t = (MODULUS - 1) / 2^s` of `MODULUS - 1`
+    const ODD_PART: UInt<LIMBS> = Self::MODULUS.shr_vartime(Self::TWO_ADICITY);
+    /// A multiplicative generator, i.e. an element of order `MODULUS - 1`, if one is known.
+    ///
+    /// Finding a generator requires factoring `MODULUS - 1`, so it is not computed: it can be given as a fourth argument to `impl_modulus!`.
+    const MULTIPLICATIVE_GENERATOR: Option<UInt<LIMBS>> = None;
+    /// A quadratic non-residue: [`ResidueParams::MULTIPLICATIVE_GENERATOR`] if it is set, otherwise the smallest one
+    const QUADRATIC_NON_RESIDUE: UInt<LIMBS> = match Self::MULTIPLICATIVE_GENERATOR {
+        Some(generator) => generator,
+        None => match find_non_residue(&Self::MODULUS) {
+            Some(non_residue) => non_residue,
+            None => panic!("no quadratic non-residue found, the modulus is not prime"),
+        },
+    };
+    /// A primitive `2^s`-th root of unity, where `s` is [`ResidueParams::TWO_ADICITY`]: `QUADRATIC_NON_RESIDUE^t`
+    const ROOT_OF_UNITY: UInt<LIMBS> = montgomery_reduction(
+        (
+            pow_montgomery_form_vartime(
+                montgomery_reduction(
+                    Self::QUADRATIC_NON_RESIDUE.mul_wide(&Self::R2),
+                    Self::MODULUS,
+                    Self::MOD_NEG_INV,
+                ),
+                &Self::ODD_PART,
+                Self::MODULUS,
+                Self::R,
+                Self::MOD_NEG_INV,
+            ),
+            UInt::ZERO,
+        ),
+        Self::MODULUS,
+        Self::MOD_NEG_INV,
+    );
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -50,6 +127,12 @@ where
 }
 
 impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Residue<MOD, LIMBS> {
+    /// The representation of 0 mod `MOD`.
+    pub const ZERO: Self = Self {
+        montgomery_form: UInt::ZERO,
+        phantom: PhantomData,
+    };
+
     /// The representation of 1 mod `MOD`.
     pub const ONE: Self = Self {
         montgomery_form: MOD::R,
@@ -70,6 +153,31 @@ impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Residue<MOD, LIMBS> {
         modular_integer
     }
 
+    /// Instantiates a new `Residue` from its Montgomery form `x * R mod MOD`, where `R = 2^(LIMBS * Word::BITS)`.
+    ///
+    /// The Montgomery form must be reduced, i.e. less than `MOD::MODULUS`; this is not checked.
+    pub const fn from_montgomery(montgomery_form: UInt<LIMBS>) -> Self {
+        Self {
+            montgomery_form,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the Montgomery form `x * R mod MOD` of this `Residue`, where `R = 2^(LIMBS * Word::BITS)`.
+    pub const fn as_montgomery(&self) -> &UInt<LIMBS> {
+        &self.montgomery_form
+    }
+
+    /// Converts a [`DynResidue`] into a `Residue`, keeping it in Montgomery form.
+    /// Returns `None` if the modulus of `residue` is not `MOD::MODULUS`.
+    pub const fn try_from_dyn(residue: &DynResidue<LIMBS>) -> Option<Self> {
+        if residue.params().modulus().ct_not_eq(&MOD::MODULUS) == 0 {
+            Some(Self::from_montgomery(*residue.as_montgomery()))
+        } else {
+            None
+        }
+    }
+
     /// Retrieves the integer currently encoded in this `Residue`, guaranteed to be reduced.
     pub const fn retrieve(&self) -> UInt<LIMBS> {
         montgomery_reduction::<LIMBS>(
@@ -78,6 +186,19 @@ impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Residue<MOD, LIMBS> {
             MOD::MOD_NEG_INV,
         )
     }
+
+    /// Computes the Legendre symbol of this residue in constant time, assuming `MOD` is an odd prime.
+    pub const fn legendre(&self) -> JacobiSymbol {
+        self.retrieve().jacobi(&MOD::MODULUS)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> zeroize::Zeroize for Residue<MOD, LIMBS> {
+    fn zeroize(&mut self) {
+        self.montgomery_form.zeroize();
+    }
 }
 
 impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> GenericResidue<LIMBS> for Residue<MOD, LIMBS> {
@@ -86,6 +207,12 @@ impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> GenericResidue<LIMBS> for Re
     }
 }
 
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> ConstantTimeEq for Residue<MOD, LIMBS> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.montgomery_form.ct_eq(&other.montgomery_form)
+    }
+}
+
 impl<MOD: ResidueParams<LIMBS> + Copy, const LIMBS: usize> ConditionallySelectable
     for Residue<MOD, LIMBS>
 {
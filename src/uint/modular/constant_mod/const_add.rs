@@ -1,4 +1,4 @@
-use core::ops::AddAssign;
+use core::{iter::Sum, ops::AddAssign};
 
 use crate::{
     modular::{add::add_montgomery_form, AddResidue},
@@ -27,6 +27,15 @@ impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Residue<MOD, LIMBS> {
     }
 }
 
+impl_residue_binary_op!(
+    [MOD: ResidueParams<LIMBS>, const LIMBS: usize] Residue<MOD, LIMBS>,
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    Residue::add
+);
+
 impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> AddAssign<&UInt<LIMBS>>
     for Residue<MOD, LIMBS>
 {
@@ -35,17 +44,27 @@ impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> AddAssign<&UInt<LIMBS>>
     }
 }
 
-impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> AddAssign<&Self> for Residue<MOD, LIMBS> {
-    fn add_assign(&mut self, rhs: &Self) {
-        *self = self.add(rhs);
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> AddAssign<UInt<LIMBS>> for Residue<MOD, LIMBS> {
+    fn add_assign(&mut self, rhs: UInt<LIMBS>) {
+        *self += &Residue::new(rhs);
+    }
+}
+
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Sum for Residue<MOD, LIMBS> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| Residue::add(&acc, &x))
+    }
+}
+
+impl<'a, MOD: ResidueParams<LIMBS>, const LIMBS: usize> Sum<&'a Self> for Residue<MOD, LIMBS> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| Residue::add(&acc, x))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        const_residue, impl_modulus, modular::constant_mod::ResidueParams, traits::Encoding, U256,
-    };
+    use crate::{const_residue, impl_modulus, U256};
 
     impl_modulus!(
         Modulus,
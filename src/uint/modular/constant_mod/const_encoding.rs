@@ -0,0 +1,148 @@
+//! Support for encoding [`Residue`]s in canonical (non-Montgomery) form.
+
+use super::{Residue, ResidueParams};
+use crate::UInt;
+
+#[cfg(feature = "serde")]
+use crate::Encoding;
+#[cfg(feature = "serde")]
+use serdect::serde::{
+    de::{Error, Unexpected},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+#[cfg(all(feature = "der", feature = "generic-array"))]
+use crate::ArrayEncoding;
+#[cfg(all(feature = "der", feature = "generic-array"))]
+use ::der::{DecodeValue, EncodeValue, FixedTag, Length, Tag};
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, MOD: ResidueParams<LIMBS>, const LIMBS: usize> Deserialize<'de> for Residue<MOD, LIMBS>
+where
+    UInt<LIMBS>: Encoding,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = UInt::<LIMBS>::deserialize(deserializer)?;
+
+        if value < MOD::MODULUS {
+            Ok(Self::new(value))
+        } else {
+            Err(D::Error::invalid_value(
+                Unexpected::Other("unreduced residue"),
+                &"an integer less than the modulus",
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Serialize for Residue<MOD, LIMBS>
+where
+    UInt<LIMBS>: Encoding,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.retrieve().serialize(serializer)
+    }
+}
+
+#[cfg(all(feature = "der", feature = "generic-array"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+impl<'a, MOD: ResidueParams<LIMBS>, const LIMBS: usize> DecodeValue<'a> for Residue<MOD, LIMBS>
+where
+    UInt<LIMBS>: ArrayEncoding,
+{
+    fn decode_value<R: der::Reader<'a>>(reader: &mut R, header: der::Header) -> der::Result<Self> {
+        let value = UInt::<LIMBS>::decode_value(reader, header)?;
+
+        if value < MOD::MODULUS {
+            Ok(Self::new(value))
+        } else {
+            Err(Tag::Integer.value_error())
+        }
+    }
+}
+
+#[cfg(all(feature = "der", feature = "generic-array"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> EncodeValue for Residue<MOD, LIMBS>
+where
+    UInt<LIMBS>: ArrayEncoding,
+{
+    fn value_len(&self) -> der::Result<Length> {
+        self.retrieve().value_len()
+    }
+
+    fn encode_value(&self, encoder: &mut dyn der::Writer) -> der::Result<()> {
+        self.retrieve().encode_value(encoder)
+    }
+}
+
+#[cfg(all(feature = "der", feature = "generic-array"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> FixedTag for Residue<MOD, LIMBS>
+where
+    UInt<LIMBS>: ArrayEncoding,
+{
+    const TAG: Tag = Tag::Integer;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        impl_modulus,
+        modular::constant_mod::{Residue, ResidueParams},
+        U256,
+    };
+
+    impl_modulus!(
+        Modulus,
+        U256,
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+    );
+
+    type R = Residue<Modulus, { U256::LIMBS }>;
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let x = R::new(U256::from_be_hex(
+            "44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56",
+        ));
+
+        let serialized = bincode::serialize(&x).unwrap();
+        assert_eq!(serialized, bincode::serialize(&x.retrieve()).unwrap());
+        let deserialized: R = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(x, deserialized);
+
+        let serialized = bincode::serialize(&Modulus::MODULUS).unwrap();
+        assert!(bincode::deserialize::<R>(&serialized).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "der", feature = "generic-array"))]
+    fn der() {
+        use der::{Decode, Encode};
+
+        let x = R::new(U256::from_be_hex(
+            "44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56",
+        ));
+
+        let mut buffer = [0u8; 64];
+        let encoded = x.encode_to_slice(&mut buffer).expect("encoding failed");
+        assert_eq!(R::from_der(encoded), Ok(x));
+
+        let mut buffer = [0u8; 64];
+        let encoded = Modulus::MODULUS
+            .encode_to_slice(&mut buffer)
+            .expect("encoding failed");
+        assert!(R::from_der(encoded).is_err());
+    }
+}
@@ -3,8 +3,8 @@ use core::marker::PhantomData;
 use subtle::{Choice, CtOption};
 
 use crate::{
-    modular::{inv::inv_montgomery_form, InvResidue},
-    Word,
+    modular::{inv::inv_montgomery_form, mul::mul_montgomery_form, InvResidue},
+    UInt, Word,
 };
 
 use super::{Residue, ResidueParams};
@@ -44,13 +44,85 @@ impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Residue<MOD, LIMBS> {
             phantom: PhantomData,
         }
     }
+
+    /// Inverts all `elements` in place using Montgomery's trick, which costs a single inversion and about `3n` multiplications.
+    ///
+    /// Zero elements are left as zero. Returns a falsy `Choice` if any element was zero, or if the product of the non-zero elements was not invertible (in which case the non-zero elements are left in an unspecified state).
+    pub fn batch_invert_array<const N: usize>(elements: &mut [Self; N]) -> Choice {
+        let mut scratch = [UInt::ZERO; N];
+        Self::batch_invert_with_scratch(elements, &mut scratch)
+    }
+
+    /// Inverts all `elements` in place using Montgomery's trick, which costs a single inversion and about `3n` multiplications.
+    ///
+    /// Zero elements are left as zero. Returns a falsy `Choice` if any element was zero, or if the product of the non-zero elements was not invertible (in which case the non-zero elements are left in an unspecified state).
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn batch_invert(elements: &mut [Self]) -> Choice {
+        let mut scratch = alloc::vec![UInt::ZERO; elements.len()];
+        Self::batch_invert_with_scratch(elements, &mut scratch)
+    }
+
+    /// Inverts all `elements` in place using Montgomery's trick, which costs a single inversion and about `3n` multiplications.
+    ///
+    /// Instead of allocating, this uses the caller-provided `scratch` space, which must have the same length as `elements`; its contents are overwritten. Panics otherwise.
+    ///
+    /// Zero elements are left as zero. Returns a falsy `Choice` if any element was zero, or if the product of the non-zero elements was not invertible (in which case the non-zero elements are left in an unspecified state).
+    pub fn batch_invert_with_scratch(elements: &mut [Self], scratch: &mut [UInt<LIMBS>]) -> Choice {
+        assert_eq!(
+            elements.len(),
+            scratch.len(),
+            "the scratch space must have the same length as the elements"
+        );
+
+        // Accumulate the prefix products, replacing zeros by one
+        let mut all_nonzero = Word::MAX;
+        let mut acc = MOD::R;
+        for (element, prefix) in elements.iter().zip(scratch.iter_mut()) {
+            *prefix = acc;
+            let nonzero = element.montgomery_form.ct_is_nonzero();
+            all_nonzero &= nonzero;
+            let factor = UInt::ct_select(MOD::R, element.montgomery_form, nonzero);
+            acc = mul_montgomery_form(&acc, &factor, MOD::MODULUS, MOD::MOD_NEG_INV);
+        }
+
+        let (mut acc, invertible) =
+            inv_montgomery_form(acc, MOD::MODULUS, &MOD::R3, MOD::MOD_NEG_INV);
+
+        // Walk back, peeling off one element at a time
+        for (element, prefix) in elements.iter_mut().zip(scratch.iter()).rev() {
+            let nonzero = element.montgomery_form.ct_is_nonzero();
+            let inverse = mul_montgomery_form(&acc, prefix, MOD::MODULUS, MOD::MOD_NEG_INV);
+            let factor = UInt::ct_select(MOD::R, element.montgomery_form, nonzero);
+            acc = mul_montgomery_form(&acc, &factor, MOD::MODULUS, MOD::MOD_NEG_INV);
+            element.montgomery_form = UInt::ct_select(element.montgomery_form, inverse, nonzero);
+        }
+
+        Choice::from((all_nonzero & invertible == Word::MAX) as u8)
+    }
+}
+
+/// Computes `lhs / rhs`, which is none if `rhs` is not invertible.
+fn div<MOD: ResidueParams<LIMBS>, const LIMBS: usize>(
+    lhs: &Residue<MOD, LIMBS>,
+    rhs: &Residue<MOD, LIMBS>,
+) -> CtOption<Residue<MOD, LIMBS>> {
+    let inverse = InvResidue::inv(*rhs);
+    let quotient = Residue::mul(lhs, &inverse.unwrap_or(Residue::ZERO));
+    CtOption::new(quotient, inverse.is_some())
 }
 
+impl_residue_binary_op!(
+    [MOD: ResidueParams<LIMBS>, const LIMBS: usize] Residue<MOD, LIMBS>,
+    CtOption<Residue<MOD, LIMBS>>,
+    Div,
+    div,
+    div
+);
+
 #[cfg(test)]
 mod tests {
-    use crate::{
-        const_residue, impl_modulus, modular::constant_mod::ResidueParams, traits::Encoding, U256,
-    };
+    use crate::{const_residue, impl_modulus, modular::constant_mod::Residue, U256};
 
     impl_modulus!(
         Modulus,
@@ -59,6 +131,7 @@ mod tests {
     );
 
     #[test]
+    #[allow(clippy::op_ref)]
     fn test_self_inverse() {
         let x =
             U256::from_be_hex("77117F1273373C26C700D076B3F780074D03339F56DD0EFB60E7F58441FD3685");
@@ -69,4 +142,68 @@ mod tests {
 
         assert_eq!(res.retrieve(), U256::ONE);
     }
+
+    #[test]
+    fn test_batch_invert() {
+        let x =
+            U256::from_be_hex("77117F1273373C26C700D076B3F780074D03339F56DD0EFB60E7F58441FD3685");
+        let y =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+        let z = U256::from(105u64);
+        let originals = [
+            const_residue!(x, Modulus),
+            const_residue!(y, Modulus),
+            const_residue!(z, Modulus),
+        ];
+
+        let mut inverses = originals;
+        assert!(bool::from(Residue::batch_invert_array(&mut inverses)));
+
+        for (original, inverse) in originals.iter().zip(inverses.iter()) {
+            assert_eq!(inverse, &original.inv());
+        }
+    }
+
+    #[test]
+    fn test_batch_invert_zero() {
+        let x =
+            U256::from_be_hex("77117F1273373C26C700D076B3F780074D03339F56DD0EFB60E7F58441FD3685");
+        let zero = U256::ZERO;
+        let x_mod = const_residue!(x, Modulus);
+        let zero_mod = const_residue!(zero, Modulus);
+
+        let mut elements = [zero_mod, x_mod, zero_mod];
+        assert!(!bool::from(Residue::batch_invert_array(&mut elements)));
+        assert_eq!(elements, [zero_mod, x_mod.inv(), zero_mod]);
+    }
+
+    #[test]
+    fn test_batch_invert_with_scratch() {
+        let x =
+            U256::from_be_hex("77117F1273373C26C700D076B3F780074D03339F56DD0EFB60E7F58441FD3685");
+        let x_mod = const_residue!(x, Modulus);
+
+        let mut elements = [x_mod, x_mod.square(), x_mod];
+        let mut scratch = [U256::ZERO; 3];
+        assert!(bool::from(Residue::batch_invert_with_scratch(
+            &mut elements[..2],
+            &mut scratch[..2]
+        )));
+        assert_eq!(elements, [x_mod.inv(), x_mod.square().inv(), x_mod]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_batch_invert_slice() {
+        let x =
+            U256::from_be_hex("77117F1273373C26C700D076B3F780074D03339F56DD0EFB60E7F58441FD3685");
+        let x_mod = const_residue!(x, Modulus);
+
+        let mut elements = [x_mod, x_mod.square()];
+        assert!(bool::from(Residue::batch_invert(&mut elements[..])));
+        assert_eq!(elements, [x_mod.inv(), x_mod.square().inv()]);
+
+        let mut empty: [Residue<Modulus, { U256::LIMBS }>; 0] = [];
+        assert!(bool::from(Residue::batch_invert(&mut empty[..])));
+    }
 }
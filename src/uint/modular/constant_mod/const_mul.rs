@@ -1,9 +1,9 @@
-use core::{
-    marker::PhantomData,
-    ops::{Mul, MulAssign},
-};
+use core::{iter::Product, marker::PhantomData};
 
-use crate::modular::{mul::mul_montgomery_form, reduction::montgomery_reduction, MulResidue};
+use crate::modular::{
+    accumulator::WideAccumulator, mul::mul_montgomery_form, reduction::montgomery_reduction,
+    MulResidue,
+};
 
 use super::{Residue, ResidueParams};
 
@@ -31,6 +31,23 @@ impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Residue<MOD, LIMBS> {
         }
     }
 
+    /// Computes the sum of the products `a[i] * b[i]`, with a single reduction at the end.
+    ///
+    /// Panics if `a` and `b` have different lengths.
+    pub fn sum_of_products(a: &[Self], b: &[Self]) -> Self {
+        assert_eq!(a.len(), b.len(), "the slices must have the same length");
+
+        let mut accumulator = WideAccumulator::new(MOD::MODULUS, MOD::MOD_NEG_INV);
+        for (a, b) in a.iter().zip(b) {
+            accumulator.add_product(&a.montgomery_form, &b.montgomery_form);
+        }
+
+        Self {
+            montgomery_form: accumulator.reduce(),
+            phantom: PhantomData,
+        }
+    }
+
     /// Computes the (reduced) square of a residue.
     pub const fn square(&self) -> Self {
         let lo_hi = self.montgomery_form.square_wide();
@@ -42,16 +59,23 @@ impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Residue<MOD, LIMBS> {
     }
 }
 
-impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> MulAssign<&Self> for Residue<MOD, LIMBS> {
-    fn mul_assign(&mut self, rhs: &Self) {
-        *self = self.mul(rhs)
+impl_residue_binary_op!(
+    [MOD: ResidueParams<LIMBS>, const LIMBS: usize] Residue<MOD, LIMBS>,
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    Residue::mul
+);
+
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Product for Residue<MOD, LIMBS> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| Residue::mul(&acc, &x))
     }
 }
 
-impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Mul for &Residue<MOD, LIMBS> {
-    type Output = Residue<MOD, LIMBS>;
-
-    fn mul(self, rhs: Self) -> Self::Output {
-        self.mul(rhs)
+impl<'a, MOD: ResidueParams<LIMBS>, const LIMBS: usize> Product<&'a Self> for Residue<MOD, LIMBS> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| Residue::mul(&acc, x))
     }
 }
@@ -0,0 +1,477 @@
+//! Polynomials with coefficients in residues with a constant modulus.
+
+use subtle::CtOption;
+
+use crate::{modular::accumulator::WideAccumulator, UInt};
+
+use super::{Residue, ResidueParams};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A polynomial of degree less than `N` with coefficients in `Residue<MOD, LIMBS>`, stored in an array from the lowest to the highest degree.
+///
+/// Unless noted otherwise, all operations run in constant time with respect to the coefficients and the evaluation points; only the capacity `N` is public.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Polynomial<MOD: ResidueParams<LIMBS>, const LIMBS: usize, const N: usize> {
+    coefficients: [Residue<MOD, LIMBS>; N],
+}
+
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize, const N: usize> Polynomial<MOD, LIMBS, N> {
+    /// The zero polynomial.
+    pub const ZERO: Self = Self {
+        coefficients: [Residue::ZERO; N],
+    };
+
+    /// Instantiates a polynomial from its coefficients, from the lowest to the highest degree.
+    pub const fn new(coefficients: [Residue<MOD, LIMBS>; N]) -> Self {
+        Self { coefficients }
+    }
+
+    /// Returns the coefficients of this polynomial, from the lowest to the highest degree.
+    pub const fn coefficients(&self) -> &[Residue<MOD, LIMBS>; N] {
+        &self.coefficients
+    }
+
+    /// Evaluates this polynomial at `x` using Horner's rule.
+    pub fn evaluate(&self, x: &Residue<MOD, LIMBS>) -> Residue<MOD, LIMBS> {
+        evaluate(&self.coefficients, x)
+    }
+
+    /// Evaluates this polynomial at `x` using Horner's rule, skipping the leading zero coefficients.
+    ///
+    /// This is variable-time with respect to the degree of the polynomial, so it must only be used with public polynomials.
+    pub fn evaluate_vartime(&self, x: &Residue<MOD, LIMBS>) -> Residue<MOD, LIMBS> {
+        evaluate_vartime(&self.coefficients, x)
+    }
+
+    /// Adds two polynomials coefficient-wise.
+    pub fn add(&self, rhs: &Self) -> Self {
+        let mut coefficients = self.coefficients;
+        add_assign(&mut coefficients, &rhs.coefficients);
+        Self { coefficients }
+    }
+
+    /// Subtracts `rhs` from `self` coefficient-wise.
+    pub fn sub(&self, rhs: &Self) -> Self {
+        let mut coefficients = self.coefficients;
+        sub_assign(&mut coefficients, &rhs.coefficients);
+        Self { coefficients }
+    }
+
+    /// Multiplies two polynomials, with a single reduction per coefficient of the product.
+    ///
+    /// Fails to compile if the product does not fit in `OUT` coefficients, i.e. if `N + M - 1 > OUT` for non-empty operands.
+    pub fn mul<const M: usize, const OUT: usize>(
+        &self,
+        rhs: &Polynomial<MOD, LIMBS, M>,
+    ) -> Polynomial<MOD, LIMBS, OUT> {
+        let () = ProductCapacity::<N, M, OUT>::ASSERT;
+
+        let mut product = Polynomial::ZERO;
+        mul(
+            &self.coefficients,
+            &rhs.coefficients,
+            &mut product.coefficients,
+        );
+        product
+    }
+
+    /// Multiplies two polynomials modulo `x^N`, i.e. discarding the coefficients of degree `N` and above.
+    ///
+    /// This type deliberately has no `Mul` operator, so that such a truncation is always explicit; use [`Polynomial::mul`] for the full product.
+    pub fn wrapping_mul(&self, rhs: &Self) -> Self {
+        let mut coefficients = [Residue::ZERO; N];
+        mul(&self.coefficients, &rhs.coefficients, &mut coefficients);
+        Self { coefficients }
+    }
+
+    /// Evaluates at `x` the unique polynomial of degree less than `N` taking the values `ys[i]` at the points `xs[i]`, using Lagrange interpolation.
+    ///
+    /// Returns none if the points `xs` are not pairwise distinct.
+    pub fn interpolate_at(
+        xs: &[Residue<MOD, LIMBS>; N],
+        ys: &[Residue<MOD, LIMBS>; N],
+        x: &Residue<MOD, LIMBS>,
+    ) -> CtOption<Residue<MOD, LIMBS>> {
+        let mut numerators = [Residue::ONE; N];
+        let mut denominators = [Residue::ONE; N];
+        lagrange_factors(xs, x, &mut numerators, &mut denominators);
+
+        let distinct = Residue::batch_invert_array(&mut denominators);
+        lagrange_combination(&mut numerators, &denominators, ys, distinct)
+    }
+
+    /// Evaluates at zero the unique polynomial of degree less than `N` taking the values `ys[i]` at the points `xs[i]`, e.g. to recover a Shamir-shared secret.
+    ///
+    /// Returns none if the points `xs` are not pairwise distinct.
+    pub fn interpolate_at_zero(
+        xs: &[Residue<MOD, LIMBS>; N],
+        ys: &[Residue<MOD, LIMBS>; N],
+    ) -> CtOption<Residue<MOD, LIMBS>> {
+        Self::interpolate_at(xs, ys, &Residue::ZERO)
+    }
+}
+
+impl_residue_binary_op!(
+    [MOD: ResidueParams<LIMBS>, const LIMBS: usize, const N: usize] Polynomial<MOD, LIMBS, N>,
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    Polynomial::add
+);
+
+impl_residue_binary_op!(
+    [MOD: ResidueParams<LIMBS>, const LIMBS: usize, const N: usize] Polynomial<MOD, LIMBS, N>,
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    Polynomial::sub
+);
+
+/// Checks at compile time that the product of polynomials with `N` and `M` coefficients fits in `OUT` coefficients.
+struct ProductCapacity<const N: usize, const M: usize, const OUT: usize>;
+
+impl<const N: usize, const M: usize, const OUT: usize> ProductCapacity<N, M, OUT> {
+    const ASSERT: () = assert!(
+        N == 0 || M == 0 || N + M - 1 <= OUT,
+        "the product does not fit in the output capacity"
+    );
+}
+
+/// A polynomial with coefficients in `Residue<MOD, LIMBS>`, stored in a vector from the lowest to the highest degree.
+///
+/// Unless noted otherwise, all operations run in constant time with respect to the coefficients and the evaluation points; only the number of coefficients is public.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VecPolynomial<MOD: ResidueParams<LIMBS>, const LIMBS: usize> {
+    coefficients: Vec<Residue<MOD, LIMBS>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> VecPolynomial<MOD, LIMBS> {
+    /// Instantiates a polynomial from its coefficients, from the lowest to the highest degree.
+    pub fn new(coefficients: Vec<Residue<MOD, LIMBS>>) -> Self {
+        Self { coefficients }
+    }
+
+    /// Returns the coefficients of this polynomial, from the lowest to the highest degree.
+    pub fn coefficients(&self) -> &[Residue<MOD, LIMBS>] {
+        &self.coefficients
+    }
+
+    /// Evaluates this polynomial at `x` using Horner's rule.
+    pub fn evaluate(&self, x: &Residue<MOD, LIMBS>) -> Residue<MOD, LIMBS> {
+        evaluate(&self.coefficients, x)
+    }
+
+    /// Evaluates this polynomial at `x` using Horner's rule, skipping the leading zero coefficients.
+    ///
+    /// This is variable-time with respect to the degree of the polynomial, so it must only be used with public polynomials.
+    pub fn evaluate_vartime(&self, x: &Residue<MOD, LIMBS>) -> Residue<MOD, LIMBS> {
+        evaluate_vartime(&self.coefficients, x)
+    }
+
+    /// Adds two polynomials coefficient-wise. The sum has as many coefficients as the longest operand.
+    pub fn add(&self, rhs: &Self) -> Self {
+        let mut coefficients = self.coefficients.clone();
+        if coefficients.len() < rhs.coefficients.len() {
+            coefficients.resize(rhs.coefficients.len(), Residue::ZERO);
+        }
+        add_assign(&mut coefficients, &rhs.coefficients);
+        Self { coefficients }
+    }
+
+    /// Subtracts `rhs` from `self` coefficient-wise. The difference has as many coefficients as the longest operand.
+    pub fn sub(&self, rhs: &Self) -> Self {
+        let mut coefficients = self.coefficients.clone();
+        if coefficients.len() < rhs.coefficients.len() {
+            coefficients.resize(rhs.coefficients.len(), Residue::ZERO);
+        }
+        sub_assign(&mut coefficients, &rhs.coefficients);
+        Self { coefficients }
+    }
+
+    /// Multiplies two polynomials, with a single reduction per coefficient of the product.
+    pub fn mul(&self, rhs: &Self) -> Self {
+        let (n, m) = (self.coefficients.len(), rhs.coefficients.len());
+        let len = if n == 0 || m == 0 { 0 } else { n + m - 1 };
+
+        let mut coefficients = alloc::vec![Residue::ZERO; len];
+        mul(&self.coefficients, &rhs.coefficients, &mut coefficients);
+        Self { coefficients }
+    }
+
+    /// Evaluates at `x` the unique polynomial of degree less than `xs.len()` taking the values `ys[i]` at the points `xs[i]`, using Lagrange interpolation.
+    ///
+    /// Returns none if the points `xs` are not pairwise distinct. Panics if `xs` and `ys` have different lengths.
+    pub fn interpolate_at(
+        xs: &[Residue<MOD, LIMBS>],
+        ys: &[Residue<MOD, LIMBS>],
+        x: &Residue<MOD, LIMBS>,
+    ) -> CtOption<Residue<MOD, LIMBS>> {
+        assert_eq!(xs.len(), ys.len(), "the slices must have the same length");
+
+        let mut numerators = alloc::vec![Residue::ONE; xs.len()];
+        let mut denominators = alloc::vec![Residue::ONE; xs.len()];
+        lagrange_factors(xs, x, &mut numerators, &mut denominators);
+
+        let distinct = Residue::batch_invert(&mut denominators);
+        lagrange_combination(&mut numerators, &denominators, ys, distinct)
+    }
+
+    /// Evaluates at zero the unique polynomial of degree less than `xs.len()` taking the values `ys[i]` at the points `xs[i]`, e.g. to recover a Shamir-shared secret.
+    ///
+    /// Returns none if the points `xs` are not pairwise distinct. Panics if `xs` and `ys` have different lengths.
+    pub fn interpolate_at_zero(
+        xs: &[Residue<MOD, LIMBS>],
+        ys: &[Residue<MOD, LIMBS>],
+    ) -> CtOption<Residue<MOD, LIMBS>> {
+        Self::interpolate_at(xs, ys, &Residue::ZERO)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize, const N: usize> From<Polynomial<MOD, LIMBS, N>>
+    for VecPolynomial<MOD, LIMBS>
+{
+    fn from(polynomial: Polynomial<MOD, LIMBS, N>) -> Self {
+        Self::new(polynomial.coefficients.to_vec())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl_residue_binary_op!(
+    [MOD: ResidueParams<LIMBS>, const LIMBS: usize] VecPolynomial<MOD, LIMBS>,
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    VecPolynomial::add
+);
+
+#[cfg(feature = "alloc")]
+impl_residue_binary_op!(
+    [MOD: ResidueParams<LIMBS>, const LIMBS: usize] VecPolynomial<MOD, LIMBS>,
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    VecPolynomial::sub
+);
+
+#[cfg(feature = "alloc")]
+impl_residue_binary_op!(
+    [MOD: ResidueParams<LIMBS>, const LIMBS: usize] VecPolynomial<MOD, LIMBS>,
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    VecPolynomial::mul
+);
+
+fn evaluate<MOD: ResidueParams<LIMBS>, const LIMBS: usize>(
+    coefficients: &[Residue<MOD, LIMBS>],
+    x: &Residue<MOD, LIMBS>,
+) -> Residue<MOD, LIMBS> {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Residue::ZERO, |acc, coefficient| {
+            Residue::add(&Residue::mul(&acc, x), coefficient)
+        })
+}
+
+fn evaluate_vartime<MOD: ResidueParams<LIMBS>, const LIMBS: usize>(
+    coefficients: &[Residue<MOD, LIMBS>],
+    x: &Residue<MOD, LIMBS>,
+) -> Residue<MOD, LIMBS> {
+    let len = coefficients
+        .iter()
+        .rposition(|coefficient| coefficient.montgomery_form != UInt::ZERO)
+        .map_or(0, |degree| degree + 1);
+    evaluate(&coefficients[..len], x)
+}
+
+/// Adds `rhs` to the first coefficients of `lhs`, which must be at least as long.
+fn add_assign<MOD: ResidueParams<LIMBS>, const LIMBS: usize>(
+    lhs: &mut [Residue<MOD, LIMBS>],
+    rhs: &[Residue<MOD, LIMBS>],
+) {
+    debug_assert!(lhs.len() >= rhs.len());
+    for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+        *lhs = Residue::add(lhs, rhs);
+    }
+}
+
+/// Subtracts `rhs` from the first coefficients of `lhs`, which must be at least as long.
+fn sub_assign<MOD: ResidueParams<LIMBS>, const LIMBS: usize>(
+    lhs: &mut [Residue<MOD, LIMBS>],
+    rhs: &[Residue<MOD, LIMBS>],
+) {
+    debug_assert!(lhs.len() >= rhs.len());
+    for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+        *lhs = Residue::sub(lhs, rhs);
+    }
+}
+
+/// Writes the product of `lhs` and `rhs` into `product`, discarding the coefficients that do not fit.
+fn mul<MOD: ResidueParams<LIMBS>, const LIMBS: usize>(
+    lhs: &[Residue<MOD, LIMBS>],
+    rhs: &[Residue<MOD, LIMBS>],
+    product: &mut [Residue<MOD, LIMBS>],
+) {
+    for (k, coefficient) in product.iter_mut().enumerate() {
+        // Only the pairs `i + j = k` with both indices in range contribute
+        let start = k.saturating_sub(rhs.len().saturating_sub(1));
+        let end = lhs.len().min(k + 1);
+
+        let mut accumulator = WideAccumulator::new(MOD::MODULUS, MOD::MOD_NEG_INV);
+        for i in start..end {
+            accumulator.add_product(&lhs[i].montgomery_form, &rhs[k - i].montgomery_form);
+        }
+        coefficient.montgomery_form = accumulator.reduce();
+    }
+}
+
+/// Computes the numerators `prod_{j != i} (x - xs[j])` and denominators `prod_{j != i} (xs[i] - xs[j])` of the Lagrange basis polynomials at `x`.
+/// `numerators` and `denominators` must be initialized to one.
+fn lagrange_factors<MOD: ResidueParams<LIMBS>, const LIMBS: usize>(
+    xs: &[Residue<MOD, LIMBS>],
+    x: &Residue<MOD, LIMBS>,
+    numerators: &mut [Residue<MOD, LIMBS>],
+    denominators: &mut [Residue<MOD, LIMBS>],
+) {
+    for (i, (numerator, denominator)) in numerators.iter_mut().zip(denominators).enumerate() {
+        for (j, x_j) in xs.iter().enumerate() {
+            if i != j {
+                *numerator = Residue::mul(numerator, &Residue::sub(x, x_j));
+                *denominator = Residue::mul(denominator, &Residue::sub(&xs[i], x_j));
+            }
+        }
+    }
+}
+
+/// Combines the values `ys` with the Lagrange basis polynomials, given their numerators and inverted denominators.
+fn lagrange_combination<MOD: ResidueParams<LIMBS>, const LIMBS: usize>(
+    numerators: &mut [Residue<MOD, LIMBS>],
+    inverted_denominators: &[Residue<MOD, LIMBS>],
+    ys: &[Residue<MOD, LIMBS>],
+    distinct: subtle::Choice,
+) -> CtOption<Residue<MOD, LIMBS>> {
+    for (numerator, inverse) in numerators.iter_mut().zip(inverted_denominators) {
+        *numerator = Residue::mul(numerator, inverse);
+    }
+
+    CtOption::new(Residue::sum_of_products(numerators, ys), distinct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Polynomial;
+    use crate::{impl_modulus, modular::constant_mod::Residue, U256};
+
+    impl_modulus!(
+        Modulus,
+        U256,
+        "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001"
+    );
+
+    type R = Residue<Modulus, { U256::LIMBS }>;
+
+    fn residue(value: u64) -> R {
+        R::new(U256::from_u64(value))
+    }
+
+    #[test]
+    fn arithmetic() {
+        // (1 + x) * (1 - x) = 1 - x^2
+        let a = Polynomial::new([residue(1), residue(1)]);
+        let b = Polynomial::new([residue(1), -residue(1)]);
+        let product: Polynomial<Modulus, { U256::LIMBS }, 3> = a.mul(&b);
+        assert_eq!(product, Polynomial::new([residue(1), R::ZERO, -residue(1)]));
+
+        assert_eq!(a + b, Polynomial::new([residue(2), R::ZERO]));
+        assert_eq!(a - b, Polynomial::new([R::ZERO, residue(2)]));
+
+        // Modulo x^2, (1 + x) * (1 - x) = 1
+        assert_eq!(a.wrapping_mul(&b), Polynomial::new([residue(1), R::ZERO]));
+        let c = Polynomial::new([residue(1), R::ZERO, residue(2)]);
+        assert_eq!(
+            c.wrapping_mul(&Polynomial::new([residue(3), residue(4), R::ZERO])),
+            Polynomial::new([residue(3), residue(4), residue(6)])
+        );
+
+        let x = residue(5);
+        assert_eq!(product.evaluate(&x), -residue(24));
+        assert_eq!(product.evaluate_vartime(&x), -residue(24));
+        assert_eq!(
+            Polynomial::new([residue(7), R::ZERO, R::ZERO]).evaluate_vartime(&x),
+            residue(7)
+        );
+    }
+
+    #[test]
+    fn interpolation() {
+        // f(x) = 1234 + 166 x + 94 x^2, the example from Wikipedia's article on Shamir's secret sharing
+        let f = Polynomial::new([residue(1234), residue(166), residue(94)]);
+        let xs = [residue(2), residue(4), residue(5)];
+        let ys = xs.map(|x| f.evaluate(&x));
+        assert_eq!(ys[0], residue(1942));
+
+        assert_eq!(
+            Polynomial::interpolate_at_zero(&xs, &ys).unwrap(),
+            residue(1234)
+        );
+        assert_eq!(
+            Polynomial::interpolate_at(&xs, &ys, &residue(6)).unwrap(),
+            f.evaluate(&residue(6))
+        );
+        assert_eq!(Polynomial::interpolate_at(&xs, &ys, &xs[1]).unwrap(), ys[1]);
+
+        let duplicates = [residue(2), residue(4), residue(2)];
+        assert!(bool::from(
+            Polynomial::interpolate_at_zero(&duplicates, &ys).is_none()
+        ));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_polynomial() {
+        use super::VecPolynomial;
+
+        let f = Polynomial::new([residue(1234), residue(166), residue(94)]);
+        let g = VecPolynomial::from(f);
+        let h = VecPolynomial::new(alloc::vec![residue(1), residue(1)]);
+
+        let product = &g * &h;
+        assert_eq!(product.coefficients().len(), 4);
+        assert_eq!(
+            product.evaluate(&residue(3)),
+            f.evaluate(&residue(3)) * residue(4)
+        );
+        assert_eq!(
+            (&g + &h).coefficients(),
+            &[residue(1235), residue(167), residue(94)]
+        );
+        assert_eq!(
+            (&h - &g).coefficients(),
+            &[-residue(1233), -residue(165), -residue(94)]
+        );
+
+        let mut k = g.clone();
+        k -= &h;
+        k += h;
+        assert_eq!(k, g);
+
+        let xs = [residue(2), residue(4), residue(5)];
+        let ys = xs.map(|x| g.evaluate(&x));
+        assert_eq!(
+            VecPolynomial::interpolate_at_zero(&xs, &ys).unwrap(),
+            residue(1234)
+        );
+    }
+}
@@ -1,26 +1,91 @@
 use crate::{
-    modular::{pow::pow_montgomery_form, PowResidue},
+    modular::{
+        pow::{
+            compute_fixed_base_powers, fixed_base_table_len, multi_exponentiate_chunked,
+            multi_exponentiate_montgomery_form, multi_exponentiate_montgomery_form_vartime,
+            pow_fixed_base_montgomery_form, pow_montgomery_form, pow_montgomery_form_vartime,
+            MultiExponentiation,
+        },
+        PowResidue,
+    },
     UInt, Word,
 };
 
 use super::{Residue, ResidueParams};
 
+/// Precomputed powers of a fixed base `Residue`, for fast constant-time exponentiation of that base.
+///
+/// Building the table costs about as much as four exponentiations; afterwards every exponentiation costs one multiplication per four exponent bits and no squarings.
+/// The powers are kept in a buffer provided by the caller, which holds [`FixedBaseTable::LEN`] residues (32 KiB for a 256-bit modulus, 2 MiB for a 2048-bit one) and is best allocated on the heap.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedBaseTable<'a, MOD: ResidueParams<LIMBS>, const LIMBS: usize> {
+    powers: &'a [UInt<LIMBS>],
+    phantom: core::marker::PhantomData<MOD>,
+}
+
+impl<'a, MOD: ResidueParams<LIMBS>, const LIMBS: usize> FixedBaseTable<'a, MOD, LIMBS> {
+    /// The number of residues the buffer of a table must hold.
+    pub const LEN: usize = fixed_base_table_len::<LIMBS>();
+
+    /// Precomputes the table of powers of `base` into `buffer`, overwriting its contents.
+    ///
+    /// Panics if `buffer` does not hold exactly [`FixedBaseTable::LEN`] residues.
+    pub fn new(base: &Residue<MOD, LIMBS>, buffer: &'a mut [UInt<LIMBS>]) -> Self {
+        compute_fixed_base_powers(
+            buffer,
+            base.montgomery_form,
+            MOD::MODULUS,
+            MOD::R,
+            MOD::MOD_NEG_INV,
+        );
+
+        Self {
+            powers: buffer,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Computes `base^exponent` in constant time, where `base` is the residue this table was built from.
+    ///
+    /// The exponent may have fewer limbs than the residue, which makes this correspondingly faster; exponents with more limbs fail to compile.
+    pub fn pow<const RHS_LIMBS: usize>(&self, exponent: &UInt<RHS_LIMBS>) -> Residue<MOD, LIMBS> {
+        Residue {
+            montgomery_form: pow_fixed_base_montgomery_form(
+                self.powers,
+                exponent,
+                MOD::MODULUS,
+                MOD::R,
+                MOD::MOD_NEG_INV,
+            ),
+            phantom: core::marker::PhantomData,
+        }
+    }
+}
+
 impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> PowResidue<LIMBS> for Residue<MOD, LIMBS> {
-    fn pow_specific(self, exponent: &UInt<LIMBS>, exponent_bits: usize) -> Self {
+    fn pow_specific<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self {
         self.pow_specific(exponent, exponent_bits)
     }
 }
 
 impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Residue<MOD, LIMBS> {
-    /// Performs modular exponentiation using Montgomery's ladder.
-    pub const fn pow(self, exponent: &UInt<LIMBS>) -> Residue<MOD, LIMBS> {
-        self.pow_specific(exponent, LIMBS * Word::BITS as usize)
+    /// Performs constant-time modular exponentiation using a fixed window.
+    /// The exponent may have a different number of limbs than the residue.
+    pub const fn pow<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+    ) -> Residue<MOD, LIMBS> {
+        self.pow_specific(exponent, RHS_LIMBS * Word::BITS as usize)
     }
 
-    /// Performs modular exponentiation using Montgomery's ladder. `exponent_bits` represents the number of bits to take into account for the exponent. Note that this value is leaked in the time pattern.
-    pub const fn pow_specific(
+    /// Performs constant-time modular exponentiation using a fixed window. `exponent_bits` represents the number of bits to take into account for the exponent. Note that this value is leaked in the time pattern.
+    pub const fn pow_specific<const RHS_LIMBS: usize>(
         self,
-        exponent: &UInt<LIMBS>,
+        exponent: &UInt<RHS_LIMBS>,
         exponent_bits: usize,
     ) -> Residue<MOD, LIMBS> {
         Self {
@@ -35,12 +100,72 @@ impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Residue<MOD, LIMBS> {
             phantom: core::marker::PhantomData,
         }
     }
+
+    /// Performs modular exponentiation using a sliding window, skipping the leading zeros of the exponent.
+    ///
+    /// This is variable-time with respect to the exponent: only use it with public exponents, e.g. to verify signatures.
+    pub const fn pow_vartime<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+    ) -> Residue<MOD, LIMBS> {
+        Self {
+            montgomery_form: pow_montgomery_form_vartime(
+                self.montgomery_form,
+                exponent,
+                MOD::MODULUS,
+                MOD::R,
+                MOD::MOD_NEG_INV,
+            ),
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Computes the product of `base^exponent` over all the given pairs in constant time.
+    ///
+    /// The bases share their squarings (Straus' method), which is considerably faster than exponentiating each base separately.
+    /// The product of no pairs is one.
+    pub fn multi_exponentiate<const RHS_LIMBS: usize>(
+        bases_and_exponents: &[(Self, UInt<RHS_LIMBS>)],
+    ) -> Self {
+        Self::multi_exponentiate_with(bases_and_exponents, multi_exponentiate_montgomery_form)
+    }
+
+    /// Computes the product of `base^exponent` over all the given pairs, like [`Residue::multi_exponentiate`].
+    ///
+    /// This is variable-time with respect to the exponents: only use it with public exponents.
+    pub fn multi_exponentiate_vartime<const RHS_LIMBS: usize>(
+        bases_and_exponents: &[(Self, UInt<RHS_LIMBS>)],
+    ) -> Self {
+        Self::multi_exponentiate_with(
+            bases_and_exponents,
+            multi_exponentiate_montgomery_form_vartime,
+        )
+    }
+
+    fn multi_exponentiate_with<const RHS_LIMBS: usize>(
+        bases_and_exponents: &[(Self, UInt<RHS_LIMBS>)],
+        multi_exponentiate: MultiExponentiation<LIMBS, RHS_LIMBS>,
+    ) -> Self {
+        Self {
+            montgomery_form: multi_exponentiate_chunked(
+                bases_and_exponents,
+                |(base, exponent)| (base.montgomery_form, *exponent),
+                multi_exponentiate,
+                MOD::MODULUS,
+                MOD::R,
+                MOD::MOD_NEG_INV,
+            ),
+            phantom: core::marker::PhantomData,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        const_residue, impl_modulus, modular::constant_mod::ResidueParams, traits::Encoding, U256,
+        const_residue, impl_modulus,
+        modular::constant_mod::{FixedBaseTable, Residue, ResidueParams},
+        U256, U64,
     };
 
     impl_modulus!(
@@ -77,6 +202,10 @@ mod tests {
         let expected =
             U256::from_be_hex("89E2A4E99F649A5AE2C18068148C355CA927B34A3245C938178ED00D6EF218AA");
         assert_eq!(res.retrieve(), expected);
+
+        // The exponent may be narrower than the modulus
+        let res = base_mod.pow(&U64::from(105u64));
+        assert_eq!(res.retrieve(), expected);
     }
 
     #[test]
@@ -94,4 +223,110 @@ mod tests {
             U256::from_be_hex("3681BC0FEA2E5D394EB178155A127B0FD2EF405486D354251C385BDD51B9D421");
         assert_eq!(res.retrieve(), expected);
     }
+
+    #[test]
+    fn test_powmod_specific() {
+        let base =
+            U256::from_be_hex("3435D18AA8313EBBE4D20002922225B53F75DC4453BB3EEC0378646F79B524A4");
+        let base_mod = const_residue!(base, Modulus);
+
+        let exponent =
+            U256::from_be_hex("77117F1273373C26C700D076B3F780074D03339F56DD0EFB60E7F58441FD3685");
+
+        // Only the lowest 130 bits of the exponent are taken into account
+        let res = base_mod.pow_specific(&exponent, 130);
+
+        let expected =
+            U256::from_be_hex("5C2695457C6F3081CF9DFC6250E44805536CE22F711E7D26E03E25C7B2F00716");
+        assert_eq!(res.retrieve(), expected);
+
+        assert_eq!(base_mod.pow_specific(&exponent, 0).retrieve(), U256::ONE);
+    }
+
+    #[test]
+    fn test_multi_exponentiate() {
+        let base =
+            U256::from_be_hex("3435D18AA8313EBBE4D20002922225B53F75DC4453BB3EEC0378646F79B524A4");
+        let base_mod = const_residue!(base, Modulus);
+        let exponent =
+            U256::from_be_hex("77117F1273373C26C700D076B3F780074D03339F56DD0EFB60E7F58441FD3685");
+
+        let base2 = U256::from(105u64);
+        let base2_mod = const_residue!(base2, Modulus);
+        let exponent2 =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+
+        let expected =
+            U256::from_be_hex("66A511DAF022790521B16B3AA7DAE2A2749FA9EFA221FAA3DE3F5CE7DEC06C1D");
+
+        let pairs = [(base_mod, exponent), (base2_mod, exponent2)];
+        assert_eq!(Residue::multi_exponentiate(&pairs).retrieve(), expected);
+        assert_eq!(
+            Residue::multi_exponentiate_vartime(&pairs).retrieve(),
+            expected
+        );
+
+        let single = [(base_mod, U256::from(105u64))];
+        assert_eq!(
+            Residue::multi_exponentiate_vartime(&single),
+            base_mod.pow(&U256::from(105u64))
+        );
+        assert_eq!(
+            Residue::<Modulus, { Modulus::LIMBS }>::multi_exponentiate::<{ U256::LIMBS }>(&[]),
+            Residue::ONE
+        );
+
+        // More pairs than are processed at once
+        let many = [(base_mod, exponent); 20];
+        assert_eq!(
+            Residue::multi_exponentiate(&many),
+            base_mod.pow(&exponent).pow(&U256::from(20u64))
+        );
+        assert_eq!(
+            Residue::multi_exponentiate_vartime(&many),
+            Residue::multi_exponentiate(&many)
+        );
+    }
+
+    #[test]
+    fn test_fixed_base_table() {
+        let base =
+            U256::from_be_hex("3435D18AA8313EBBE4D20002922225B53F75DC4453BB3EEC0378646F79B524A4");
+        let base_mod = const_residue!(base, Modulus);
+        let mut buffer = [U256::ZERO; FixedBaseTable::<Modulus, { U256::LIMBS }>::LEN];
+        let table = FixedBaseTable::new(&base_mod, &mut buffer);
+
+        let exponent =
+            U256::from_be_hex("77117F1273373C26C700D076B3F780074D03339F56DD0EFB60E7F58441FD3685");
+        let expected =
+            U256::from_be_hex("3681BC0FEA2E5D394EB178155A127B0FD2EF405486D354251C385BDD51B9D421");
+        assert_eq!(table.pow(&exponent).retrieve(), expected);
+
+        assert_eq!(table.pow(&U256::ZERO), Residue::ONE);
+        assert_eq!(table.pow(&U256::ONE), base_mod);
+        assert_eq!(table.pow(&U256::MAX), base_mod.pow(&U256::MAX));
+
+        // Narrower exponents only use the first windows of the table
+        let exponent = U64::from_u64(0x77117F1273373C26);
+        assert_eq!(table.pow(&exponent), base_mod.pow(&exponent));
+        assert_eq!(table.pow(&U64::ZERO), Residue::ONE);
+    }
+
+    #[test]
+    fn test_powmod_vartime() {
+        let base =
+            U256::from_be_hex("3435D18AA8313EBBE4D20002922225B53F75DC4453BB3EEC0378646F79B524A4");
+        let base_mod = const_residue!(base, Modulus);
+
+        let exponent =
+            U256::from_be_hex("77117F1273373C26C700D076B3F780074D03339F56DD0EFB60E7F58441FD3685");
+        let expected =
+            U256::from_be_hex("3681BC0FEA2E5D394EB178155A127B0FD2EF405486D354251C385BDD51B9D421");
+        assert_eq!(base_mod.pow_vartime(&exponent).retrieve(), expected);
+
+        for exponent in [0u64, 1, 2, 3, 105, 65537, u64::MAX] {
+            let exponent = U64::from(exponent);
+            assert_eq!(base_mod.pow_vartime(&exponent), base_mod.pow(&exponent));
+        }
+    }
 }
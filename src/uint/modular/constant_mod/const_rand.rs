@@ -0,0 +1,68 @@
+//! Random residue generation with a constant modulus
+
+use core::marker::PhantomData;
+
+use rand_core::{CryptoRng, RngCore};
+
+use super::{Residue, ResidueParams};
+use crate::{NonZero, Random, RandomMod, UInt};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Random for Residue<MOD, LIMBS> {
+    /// Generate a cryptographically secure, uniformly random [`Residue`].
+    ///
+    /// Since multiplication by `R` is a bijection, the Montgomery form is sampled directly.
+    fn random(rng: impl CryptoRng + RngCore) -> Self {
+        let modulus = NonZero::new(MOD::MODULUS).unwrap();
+        Self {
+            montgomery_form: UInt::random_mod(rng, &modulus),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Residue<MOD, LIMBS> {
+    /// Generate a cryptographically secure, uniformly random non-zero [`Residue`].
+    ///
+    /// Panics if `MOD::MODULUS` is 1, since there are no non-zero residues then.
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+    pub fn random_nonzero(rng: impl CryptoRng + RngCore) -> Self {
+        // The Montgomery form is zero only for the zero residue, so sample it from [1, modulus)
+        let bound = NonZero::new(MOD::MODULUS.wrapping_sub(&UInt::ONE)).unwrap();
+        Self {
+            montgomery_form: UInt::random_mod(rng, &bound).wrapping_add(&UInt::ONE),
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::SeedableRng;
+
+    use crate::{
+        impl_modulus,
+        modular::constant_mod::{Residue, ResidueParams},
+        Random, U256,
+    };
+
+    impl_modulus!(
+        Modulus,
+        U256,
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+    );
+
+    #[test]
+    fn random() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+
+        let x = Residue::<Modulus, { U256::LIMBS }>::random(&mut rng);
+        assert!(x.as_montgomery() < &Modulus::MODULUS);
+        assert!(x.retrieve() < Modulus::MODULUS);
+
+        let y = Residue::<Modulus, { U256::LIMBS }>::random_nonzero(&mut rng);
+        assert!(y.as_montgomery() < &Modulus::MODULUS);
+        assert_ne!(y.retrieve(), U256::ZERO);
+        assert_ne!(x, y);
+    }
+}
@@ -0,0 +1,99 @@
+use core::marker::PhantomData;
+
+use subtle::{Choice, CtOption};
+
+use crate::{
+    modular::{sqrt::sqrt_montgomery_form, SqrtResidue},
+    Word,
+};
+
+use super::{Residue, ResidueParams};
+
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> Residue<MOD, LIMBS> {
+    const ROOT_OF_UNITY: Self = Self::new(MOD::ROOT_OF_UNITY);
+}
+
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> SqrtResidue for Residue<MOD, LIMBS> {
+    fn sqrt(&self) -> CtOption<Self> {
+        let (montgomery_form, is_some) = sqrt_montgomery_form(
+            self.montgomery_form,
+            MOD::MODULUS,
+            MOD::R,
+            MOD::MOD_NEG_INV,
+            MOD::TWO_ADICITY,
+            &MOD::ODD_PART,
+            &Self::ROOT_OF_UNITY.montgomery_form,
+        );
+
+        let value = Self {
+            montgomery_form,
+            phantom: PhantomData,
+        };
+
+        CtOption::new(value, Choice::from((is_some == Word::MAX) as u8))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{const_residue, impl_modulus, modular::SqrtResidue, U256};
+
+    // p = 3 mod 4
+    impl_modulus!(
+        P256,
+        U256,
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+    );
+
+    // p = 5 mod 8
+    impl_modulus!(
+        Curve25519,
+        U256,
+        "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"
+    );
+
+    // p = 1 mod 2^32
+    impl_modulus!(
+        Bls12381Scalar,
+        U256,
+        "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001"
+    );
+
+    macro_rules! test_sqrt {
+        ($test_name:ident, $modulus:ident) => {
+            #[test]
+            fn $test_name() {
+                let x = U256::from_be_hex(
+                    "44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56",
+                );
+                let x_mod = const_residue!(x, $modulus);
+                let square = x_mod.square();
+
+                let root = square.sqrt().unwrap();
+                assert_eq!(root.square(), square);
+                assert!(root == x_mod || root == -x_mod);
+
+                let zero = U256::ZERO;
+                let zero_mod = const_residue!(zero, $modulus);
+                assert_eq!(zero_mod.sqrt().unwrap(), zero_mod);
+            }
+        };
+    }
+
+    test_sqrt!(sqrt_3_mod_4, P256);
+    test_sqrt!(sqrt_5_mod_8, Curve25519);
+    test_sqrt!(sqrt_tonelli_shanks, Bls12381Scalar);
+
+    #[test]
+    fn sqrt_non_residue() {
+        // 2 is a quadratic non-residue modulo 2^255 - 19
+        let two = U256::from(2u64);
+        let two_mod = const_residue!(two, Curve25519);
+        assert!(bool::from(two_mod.sqrt().is_none()));
+
+        // 7 is a quadratic non-residue modulo the BLS12-381 scalar field
+        let seven = U256::from(7u64);
+        let seven_mod = const_residue!(seven, Bls12381Scalar);
+        assert!(bool::from(seven_mod.sqrt().is_none()));
+    }
+}
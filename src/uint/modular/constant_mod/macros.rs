@@ -1,36 +1,30 @@
 // TODO: Use `adt_const_params` once stabilized to make a `Residue` generic around a modulus rather than having to implement a ZST + trait
 #[macro_export]
-/// Implements a modulus with the given name, type, and value, in that specific order. Please `use crypto_bigint::traits::Encoding` to make this work.
+/// Implements a modulus with the given name, type, and value, in that specific order.
 /// For example, `impl_modulus!(MyModulus, U256, "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001");` implements a 256-bit modulus named `MyModulus`.
+///
+/// A multiplicative generator, i.e. an element of order `modulus - 1`, can be given as a fourth `u64` argument, which sets `ResidueParams::MULTIPLICATIVE_GENERATOR`. Compilation fails if the modulus is even.
 macro_rules! impl_modulus {
-    ($name:ident, $uint_type:ty, $value:expr) => {
+    ($name:ident, $uint_type:ty, $value:expr $(, $generator:expr)?) => {
         #[derive(Clone, Copy, PartialEq, Eq, Debug)]
         pub struct $name {}
-        impl<const DLIMBS: usize> ResidueParams<{ nlimbs!(<$uint_type>::BIT_SIZE) }> for $name
+        const _: () = assert!(
+            <$uint_type>::from_be_hex($value).bit_vartime(0) == 1,
+            "the modulus must be odd"
+        );
+        impl<const DLIMBS: usize>
+            $crate::modular::constant_mod::ResidueParams<{ <$uint_type>::LIMBS }> for $name
         where
-            $crate::UInt<{ nlimbs!(<$uint_type>::BIT_SIZE) }>:
-                $crate::traits::Concat<Output = $crate::UInt<DLIMBS>>,
-            $crate::UInt<DLIMBS>: $crate::traits::Split<Output = $uint_type>,
+            $crate::UInt<{ <$uint_type>::LIMBS }>: $crate::Concat<Output = $crate::UInt<DLIMBS>>,
+            $crate::UInt<DLIMBS>: $crate::Split<Output = $uint_type>,
         {
-            const LIMBS: usize = { nlimbs!(<$uint_type>::BIT_SIZE) };
-            const MODULUS: $crate::UInt<{ nlimbs!(<$uint_type>::BIT_SIZE) }> =
+            const LIMBS: usize = <$uint_type>::LIMBS;
+            const MODULUS: $crate::UInt<{ <$uint_type>::LIMBS }> =
                 <$uint_type>::from_be_hex($value);
-            const R: $crate::UInt<{ nlimbs!(<$uint_type>::BIT_SIZE) }> = $crate::UInt::MAX
-                .ct_reduce(&Self::MODULUS)
-                .0
-                .wrapping_add(&$crate::UInt::ONE);
-            const R2: $crate::UInt<{ nlimbs!(<$uint_type>::BIT_SIZE) }> =
-                $crate::UInt::ct_reduce_wide(Self::R.square_wide(), &Self::MODULUS).0;
-            const MOD_NEG_INV: $crate::Limb = $crate::Limb(
-                $crate::Word::MIN
-                    .wrapping_sub(Self::MODULUS.inv_mod2k($crate::Word::BITS as usize).limbs[0].0),
-            );
-            const R3: $crate::UInt<{ nlimbs!(<$uint_type>::BIT_SIZE) }> =
-                $crate::uint::modular::reduction::montgomery_reduction(
-                    Self::R2.square_wide(),
-                    Self::MODULUS,
-                    Self::MOD_NEG_INV,
-                );
+            $(
+                const MULTIPLICATIVE_GENERATOR: Option<$crate::UInt<{ <$uint_type>::LIMBS }>> =
+                    Some($crate::UInt::from_u64($generator));
+            )?
         }
     };
 }
@@ -39,9 +33,334 @@ macro_rules! impl_modulus {
 /// Creates a `Residue` with the given value for a specific modulus.
 /// For example, `residue!(U256::from(105u64), MyModulus);` creates a `Residue` for 105 mod `MyModulus`.
 macro_rules! const_residue {
-    ($variable:ident, $modulus:ident) => {
-        $crate::uint::modular::constant_mod::Residue::<$modulus, { $modulus::LIMBS }>::new(
-            $variable,
-        )
+    ($variable:ident, $modulus:ident) => {{
+        use $crate::modular::constant_mod::ResidueParams as _;
+        $crate::modular::constant_mod::Residue::<$modulus, { $modulus::LIMBS }>::new($variable)
+    }};
+}
+
+#[cfg(feature = "ff")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ff")))]
+#[macro_export]
+/// Implements a modulus as in `impl_modulus!`, together with a prime field type wrapping `Residue` which implements `ff::Field` and `ff::PrimeField`.
+///
+/// The arguments are the name of the field type, then the name, type and value of the modulus as for `impl_modulus!`, and finally a multiplicative generator of the field as a `u64`. The modulus must be prime.
+/// As required by `ff::PrimeField::MULTIPLICATIVE_GENERATOR`, the generator must have order `modulus - 1`, i.e. generate the whole multiplicative group, which can be computed with SageMath as `GF(modulus).primitive_element()`.
+/// `ROOT_OF_UNITY` is derived from it, and compilation fails if it is not a quadratic non-residue, but its order is not checked.
+/// For example, `impl_prime_field!(Scalar, ScalarModulus, U256, "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001", 7);` implements the scalar field of BLS12-381.
+///
+/// Field elements are represented by the big-endian encoding of their canonical (non-Montgomery) form.
+macro_rules! impl_prime_field {
+    ($field:ident, $modulus:ident, $uint_type:ty, $value:expr, $generator:expr) => {
+        $crate::impl_modulus!($modulus, $uint_type, $value, $generator);
+
+        #[doc = concat!("An element of the prime field modulo [`", stringify!($modulus), "`].")]
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        pub struct $field(
+            pub $crate::modular::constant_mod::Residue<$modulus, { <$uint_type>::LIMBS }>,
+        );
+
+        const _: () = {
+            use $crate::{
+                ff::{Field, PrimeField},
+                modular::{
+                    constant_mod::{Residue, ResidueParams},
+                    InvResidue, SqrtResidue,
+                },
+                subtle::{
+                    Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeLess, CtOption,
+                },
+                ArrayEncoding, ByteArray, Encoding, Integer,
+            };
+
+            type R = Residue<$modulus, { <$uint_type>::LIMBS }>;
+            type U = $crate::UInt<{ <$uint_type>::LIMBS }>;
+
+            const MODULUS: U = <$modulus>::MODULUS;
+            const GENERATOR: R = R::new(U::from_u64($generator));
+            const ROOT_OF_UNITY: R = R::new(<$modulus>::ROOT_OF_UNITY);
+            const _: () = assert!(
+                GENERATOR.legendre().to_i8() == -1,
+                "the generator must be a quadratic non-residue"
+            );
+
+            impl From<R> for $field {
+                fn from(residue: R) -> Self {
+                    Self(residue)
+                }
+            }
+
+            impl From<$field> for R {
+                fn from(element: $field) -> Self {
+                    element.0
+                }
+            }
+
+            impl Default for $field {
+                fn default() -> Self {
+                    Self(R::ZERO)
+                }
+            }
+
+            impl ConditionallySelectable for $field {
+                fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                    Self(R::conditional_select(&a.0, &b.0, choice))
+                }
+            }
+
+            impl ConstantTimeEq for $field {
+                fn ct_eq(&self, other: &Self) -> Choice {
+                    self.0.ct_eq(&other.0)
+                }
+            }
+
+            impl core::ops::Neg for $field {
+                type Output = Self;
+
+                fn neg(self) -> Self {
+                    Self(R::neg(&self.0))
+                }
+            }
+
+            fn add(lhs: &$field, rhs: &$field) -> $field {
+                $field(R::add(&lhs.0, &rhs.0))
+            }
+
+            fn sub(lhs: &$field, rhs: &$field) -> $field {
+                $field(R::sub(&lhs.0, &rhs.0))
+            }
+
+            fn mul(lhs: &$field, rhs: &$field) -> $field {
+                $field(R::mul(&lhs.0, &rhs.0))
+            }
+
+            $crate::impl_residue_binary_op!(
+                [] $field,
+                Add,
+                add,
+                AddAssign,
+                add_assign,
+                add
+            );
+
+            $crate::impl_residue_binary_op!(
+                [] $field,
+                Sub,
+                sub,
+                SubAssign,
+                sub_assign,
+                sub
+            );
+
+            $crate::impl_residue_binary_op!(
+                [] $field,
+                Mul,
+                mul,
+                MulAssign,
+                mul_assign,
+                mul
+            );
+
+            impl core::iter::Sum for $field {
+                fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self::ZERO, |acc, x| acc + x)
+                }
+            }
+
+            impl<'a> core::iter::Sum<&'a $field> for $field {
+                fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                    iter.fold(Self::ZERO, |acc, x| acc + x)
+                }
+            }
+
+            impl core::iter::Product for $field {
+                fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self::ONE, |acc, x| acc * x)
+                }
+            }
+
+            impl<'a> core::iter::Product<&'a $field> for $field {
+                fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                    iter.fold(Self::ONE, |acc, x| acc * x)
+                }
+            }
+
+            impl Field for $field {
+                const ZERO: Self = Self(R::ZERO);
+                const ONE: Self = Self(R::ONE);
+
+                fn random(mut rng: impl $crate::rand_core::RngCore) -> Self {
+                    // Rejection sampling of the Montgomery form, which is uniform since multiplication by `R` is a bijection
+                    let shift = U::BIT_SIZE - MODULUS.bits_vartime();
+                    let mut bytes = ByteArray::<U>::default();
+                    loop {
+                        rng.fill_bytes(&mut bytes);
+                        let montgomery_form = U::from_be_byte_array(bytes).shr_vartime(shift);
+                        if montgomery_form < MODULUS {
+                            return Self(R::from_montgomery(montgomery_form));
+                        }
+                    }
+                }
+
+                fn square(&self) -> Self {
+                    Self(R::square(&self.0))
+                }
+
+                fn double(&self) -> Self {
+                    Self(R::add(&self.0, &self.0))
+                }
+
+                fn invert(&self) -> CtOption<Self> {
+                    let inverse = InvResidue::inv(self.0);
+                    CtOption::new(Self(inverse.unwrap_or(R::ZERO)), inverse.is_some())
+                }
+
+                fn sqrt(&self) -> CtOption<Self> {
+                    let root = SqrtResidue::sqrt(&self.0);
+                    CtOption::new(Self(root.unwrap_or(R::ZERO)), root.is_some())
+                }
+
+                fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+                    let div_inverse = InvResidue::inv(div.0);
+                    let ratio = R::mul(&num.0, &div_inverse.unwrap_or(R::ZERO));
+
+                    // If the ratio is not a square, its product with the (non-square) root of unity is,
+                    // so that a single square root is needed
+                    let is_square = !ratio.legendre().is_minus_one();
+                    let square =
+                        R::conditional_select(&R::mul(&ROOT_OF_UNITY, &ratio), &ratio, is_square);
+                    let root = SqrtResidue::sqrt(&square).unwrap_or(R::ZERO);
+
+                    (
+                        is_square & (div_inverse.is_some() | num.is_zero()),
+                        Self(root),
+                    )
+                }
+            }
+
+            impl From<u64> for $field {
+                fn from(value: u64) -> Self {
+                    Self(R::new(U::from_u64(value)))
+                }
+            }
+
+            impl PrimeField for $field {
+                type Repr = ByteArray<U>;
+
+                fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+                    let integer = U::from_be_byte_array(repr);
+                    CtOption::new(Self(R::new(integer)), integer.ct_lt(&MODULUS))
+                }
+
+                fn to_repr(&self) -> Self::Repr {
+                    self.0.retrieve().to_be_byte_array()
+                }
+
+                fn is_odd(&self) -> Choice {
+                    self.0.retrieve().is_odd()
+                }
+
+                const MODULUS: &'static str = $value;
+                const NUM_BITS: u32 = MODULUS.bits_vartime() as u32;
+                const CAPACITY: u32 = Self::NUM_BITS - 1;
+                const TWO_INV: Self = Self(R::new(U::from_u8(2)).inv());
+                const MULTIPLICATIVE_GENERATOR: Self = Self(GENERATOR);
+                const S: u32 = <$modulus>::TWO_ADICITY as u32;
+                const ROOT_OF_UNITY: Self = Self(ROOT_OF_UNITY);
+                const ROOT_OF_UNITY_INV: Self = Self(ROOT_OF_UNITY.inv());
+                const DELTA: Self =
+                    Self(GENERATOR.pow_vartime(&U::ONE.shl_vartime(<$modulus>::TWO_ADICITY)));
+            }
+        };
     };
 }
+
+#[cfg(all(test, feature = "ff"))]
+mod tests {
+    use ff::{Field, PrimeField};
+    use rand_core::SeedableRng;
+
+    use crate::{modular::constant_mod::ResidueParams, traits::Encoding, U256};
+
+    impl_prime_field!(
+        Scalar,
+        ScalarModulus,
+        U256,
+        "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+        7
+    );
+
+    impl_prime_field!(
+        FieldElement,
+        FieldModulus,
+        U256,
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+        6
+    );
+
+    fn check_constants<F: PrimeField>() {
+        assert_eq!(F::TWO_INV.double(), F::ONE);
+        assert_eq!(F::ROOT_OF_UNITY * F::ROOT_OF_UNITY_INV, F::ONE);
+        assert_eq!(F::ROOT_OF_UNITY.pow_vartime([1u64 << F::S]), F::ONE);
+        assert_eq!(F::ROOT_OF_UNITY.pow_vartime([1u64 << (F::S - 1)]), -F::ONE);
+        assert_eq!(
+            F::DELTA,
+            F::MULTIPLICATIVE_GENERATOR.pow_vartime([1u64 << F::S])
+        );
+        assert!(bool::from(F::MULTIPLICATIVE_GENERATOR.sqrt().is_none()));
+    }
+
+    fn check_arithmetic<F: PrimeField>() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        let x = F::random(&mut rng);
+        let y = F::random(&mut rng);
+        assert_ne!(x, y);
+
+        assert_eq!(x * x.invert().unwrap(), F::ONE);
+        assert!(bool::from(F::ZERO.invert().is_none()));
+        assert_eq!(x.square().sqrt().unwrap().square(), x.square());
+        assert_eq!([x, y].iter().sum::<F>(), x + y);
+        assert_eq!([x, y].into_iter().product::<F>(), x * y);
+        assert_eq!(F::from_repr(x.to_repr()).unwrap(), x);
+
+        let (is_square, root) = F::sqrt_ratio(&x.square(), &y.square());
+        assert!(bool::from(is_square));
+        assert_eq!(root.square() * y.square(), x.square());
+        let non_square = x.square() * F::MULTIPLICATIVE_GENERATOR;
+        let (is_square, root) = F::sqrt_ratio(&non_square, &y.square());
+        assert!(!bool::from(is_square));
+        assert_eq!(root.square() * y.square(), non_square * F::ROOT_OF_UNITY);
+        assert!(bool::from(F::sqrt_ratio(&F::ZERO, &F::ZERO).0));
+        assert!(!bool::from(F::sqrt_ratio(&F::ONE, &F::ZERO).0));
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(Scalar::NUM_BITS, 255);
+        assert_eq!(Scalar::S, 32);
+        assert_eq!(
+            Scalar::ROOT_OF_UNITY.0.retrieve(),
+            U256::from_be_hex("16a2a19edfe81f20d09b681922c813b4b63683508c2280b93829971f439f0d2b")
+        );
+        check_constants::<Scalar>();
+
+        assert_eq!(FieldElement::NUM_BITS, 256);
+        assert_eq!(FieldElement::S, 1);
+        assert_eq!(FieldElement::ROOT_OF_UNITY, -FieldElement::ONE);
+        check_constants::<FieldElement>();
+    }
+
+    #[test]
+    fn arithmetic() {
+        check_arithmetic::<Scalar>();
+        check_arithmetic::<FieldElement>();
+    }
+
+    #[test]
+    fn repr() {
+        let modulus = ScalarModulus::MODULUS.to_be_bytes();
+        assert!(bool::from(Scalar::from_repr(modulus.into()).is_none()));
+        assert_eq!(Scalar::from(5u64).to_repr()[31], 5);
+        assert!(bool::from(Scalar::from(5u64).is_odd()));
+    }
+}
@@ -2,38 +2,343 @@ use crate::{Limb, UInt, Word};
 
 use super::mul::{mul_montgomery_form, square_montgomery_form};
 
-/// Performs modular exponentiation using Montgomery's ladder. `exponent_bits` represents the number of bits to take into account for the exponent. Note that this value is leaked in the time pattern.
-pub const fn pow_montgomery_form<const LIMBS: usize>(
+/// The number of exponent bits processed per multiplication in the fixed-window exponentiation.
+pub(crate) const WINDOW: usize = 4;
+const WINDOW_MASK: Word = (1 << WINDOW) - 1;
+
+/// A table of the powers `x^0` to `x^(2^WINDOW - 1)` of a residue, in Montgomery form.
+pub(crate) type PowerTable<const LIMBS: usize> = [UInt<LIMBS>; 1 << WINDOW];
+
+/// The number of residues in a fixed-base table: one [`PowerTable`] for every window of an exponent.
+pub(crate) const fn fixed_base_table_len<const LIMBS: usize>() -> usize {
+    LIMBS * Limb::BIT_SIZE / WINDOW * (1 << WINDOW)
+}
+
+/// Performs modular exponentiation using a fixed window of `WINDOW` bits. `exponent_bits` represents the number of bits to take into account for the exponent. Note that this value is leaked in the time pattern.
+///
+/// Every window costs `WINDOW` squarings and one multiplication, and the table entry is selected by scanning the whole table, so neither the timing nor the memory access pattern depends on the exponent.
+pub const fn pow_montgomery_form<const LIMBS: usize, const RHS_LIMBS: usize>(
     x: UInt<LIMBS>,
-    exponent: &UInt<LIMBS>,
+    exponent: &UInt<RHS_LIMBS>,
     exponent_bits: usize,
     modulus: UInt<LIMBS>,
     r: UInt<LIMBS>,
     mod_neg_inv: Limb,
 ) -> UInt<LIMBS> {
-    let mut x1: UInt<LIMBS> = r;
-    let mut x2: UInt<LIMBS> = x;
+    if exponent_bits == 0 {
+        return r; // 1 in Montgomery form
+    }
 
-    // Shift the exponent all the way to the left so the leftmost bit is the MSB of the `UInt`
-    let mut n: UInt<LIMBS> = exponent.shl_vartime((LIMBS * Word::BITS as usize) - exponent_bits);
+    let powers = compute_powers(x, modulus, r, mod_neg_inv);
 
-    let mut i = 0;
-    while i < exponent_bits {
-        // Peel off one bit at a time from the left side
-        let (next_n, overflow) = n.shl_1();
-        n = next_n;
+    let mut z = r;
+    let mut window_num = (exponent_bits + WINDOW - 1) / WINDOW;
+    let top_window = window_num - 1;
+
+    while window_num > 0 {
+        window_num -= 1;
+
+        let bit = window_num * WINDOW;
+        let mut idx = window_index(exponent, bit);
+
+        if window_num == top_window {
+            // Only take the requested bits into account, and skip the squarings of 1
+            if exponent_bits - bit < WINDOW {
+                idx &= (1 << (exponent_bits - bit)) - 1;
+            }
+        } else {
+            z = square_window(z, modulus, mod_neg_inv);
+        }
+
+        let power = ct_lookup(&powers, idx);
+        z = mul_montgomery_form(&z, &power, modulus, mod_neg_inv);
+    }
+
+    z
+}
+
+/// Performs modular exponentiation using a sliding window, skipping the leading zeros of the exponent.
+///
+/// This is variable-time with respect to the exponent, so it must only be used with public exponents.
+pub const fn pow_montgomery_form_vartime<const LIMBS: usize, const RHS_LIMBS: usize>(
+    x: UInt<LIMBS>,
+    exponent: &UInt<RHS_LIMBS>,
+    modulus: UInt<LIMBS>,
+    r: UInt<LIMBS>,
+    mod_neg_inv: Limb,
+) -> UInt<LIMBS> {
+    let exponent_bits = exponent.bits_vartime();
+
+    // Small exponents (such as 65537) are better served by plain square-and-multiply
+    let window = if exponent_bits <= 32 { 1 } else { WINDOW };
+
+    // odd_powers[i] contains x^(2i + 1)
+    let mut odd_powers = [x; 1 << (WINDOW - 1)];
+    if window > 1 {
+        let x2 = square_montgomery_form(&x, modulus, mod_neg_inv);
+        let mut i = 1;
+        while i < odd_powers.len() {
+            odd_powers[i] = mul_montgomery_form(&odd_powers[i - 1], &x2, modulus, mod_neg_inv);
+            i += 1;
+        }
+    }
+
+    let mut z = r;
+    let mut started = false;
+    let mut i = exponent_bits;
+    while i > 0 {
+        if exponent.bit_vartime(i - 1) == 0 {
+            z = square_montgomery_form(&z, modulus, mod_neg_inv);
+            i -= 1;
+            continue;
+        }
+
+        // Find the longest window ending in a set bit, covering bits `j..i`
+        let mut j = i.saturating_sub(window);
+        while exponent.bit_vartime(j) == 0 {
+            j += 1;
+        }
+
+        let mut value = 0;
+        while i > j {
+            i -= 1;
+            value = (value << 1) | exponent.bit_vartime(i) as usize;
+            if started {
+                z = square_montgomery_form(&z, modulus, mod_neg_inv);
+            }
+        }
+
+        z = if started {
+            mul_montgomery_form(&z, &odd_powers[value >> 1], modulus, mod_neg_inv)
+        } else {
+            odd_powers[value >> 1]
+        };
+        started = true;
+    }
+
+    z
+}
+
+/// The number of bases processed together in multi-exponentiation, bounding the size of the tables kept on the stack.
+const MULTI_EXPONENTIATION_CHUNK: usize = 8;
+
+/// The signature shared by [`multi_exponentiate_montgomery_form`] and [`multi_exponentiate_montgomery_form_vartime`].
+pub(crate) type MultiExponentiation<const LIMBS: usize, const RHS_LIMBS: usize> = fn(
+    &[(UInt<LIMBS>, UInt<RHS_LIMBS>)],
+    &mut [PowerTable<LIMBS>],
+    UInt<LIMBS>,
+    UInt<LIMBS>,
+    Limb,
+)
+    -> UInt<LIMBS>;
+
+/// Computes the product of `base_i^exponent_i` with `multi_exponentiate`, on chunks of at most [`MULTI_EXPONENTIATION_CHUNK`] pairs so that no allocation is needed.
+/// `to_montgomery_pair` returns the base (in Montgomery form) and exponent of an item.
+///
+/// Each chunk has its own squarings, which only matters for more than [`MULTI_EXPONENTIATION_CHUNK`] pairs. An empty input yields one.
+pub(crate) fn multi_exponentiate_chunked<T, const LIMBS: usize, const RHS_LIMBS: usize>(
+    bases_and_exponents: &[T],
+    to_montgomery_pair: impl Fn(&T) -> (UInt<LIMBS>, UInt<RHS_LIMBS>),
+    multi_exponentiate: MultiExponentiation<LIMBS, RHS_LIMBS>,
+    modulus: UInt<LIMBS>,
+    r: UInt<LIMBS>,
+    mod_neg_inv: Limb,
+) -> UInt<LIMBS> {
+    let mut z = r;
+    for chunk in bases_and_exponents.chunks(MULTI_EXPONENTIATION_CHUNK) {
+        let mut pairs = [(UInt::ZERO, UInt::ZERO); MULTI_EXPONENTIATION_CHUNK];
+        for (pair, item) in pairs.iter_mut().zip(chunk) {
+            *pair = to_montgomery_pair(item);
+        }
+        let mut tables = [[UInt::ZERO; 1 << WINDOW]; MULTI_EXPONENTIATION_CHUNK];
+
+        let product =
+            multi_exponentiate(&pairs[..chunk.len()], &mut tables, modulus, r, mod_neg_inv);
+        z = mul_montgomery_form(&z, &product, modulus, mod_neg_inv);
+    }
+
+    z
+}
+
+/// Computes the product of `base_i^exponent_i` using Straus' method: all bases share the same squarings, and each window costs one multiplication per base.
+/// The bases are expected in Montgomery form and `tables` must be at least as long as `bases_and_exponents`.
+///
+/// The full width of the exponents is always processed and table entries are selected by scanning the whole table, so the exponents are not leaked.
+pub(crate) fn multi_exponentiate_montgomery_form<const LIMBS: usize, const RHS_LIMBS: usize>(
+    bases_and_exponents: &[(UInt<LIMBS>, UInt<RHS_LIMBS>)],
+    tables: &mut [PowerTable<LIMBS>],
+    modulus: UInt<LIMBS>,
+    r: UInt<LIMBS>,
+    mod_neg_inv: Limb,
+) -> UInt<LIMBS> {
+    for ((base, _), table) in bases_and_exponents.iter().zip(tables.iter_mut()) {
+        *table = compute_powers(*base, modulus, r, mod_neg_inv);
+    }
+
+    let mut z = r;
+    let mut window_num = RHS_LIMBS * Limb::BIT_SIZE / WINDOW;
+    let top_window = window_num - 1;
+
+    while window_num > 0 {
+        window_num -= 1;
+
+        if window_num != top_window {
+            z = square_window(z, modulus, mod_neg_inv);
+        }
+
+        for ((_, exponent), table) in bases_and_exponents.iter().zip(tables.iter()) {
+            let power = ct_lookup(table, window_index(exponent, window_num * WINDOW));
+            z = mul_montgomery_form(&z, &power, modulus, mod_neg_inv);
+        }
+    }
+
+    z
+}
+
+/// Variable-time version of [`multi_exponentiate_montgomery_form`]. Leading zero windows of the exponents are skipped, as are multiplications by zero windows, so the exponents are leaked in the time pattern.
+pub(crate) fn multi_exponentiate_montgomery_form_vartime<
+    const LIMBS: usize,
+    const RHS_LIMBS: usize,
+>(
+    bases_and_exponents: &[(UInt<LIMBS>, UInt<RHS_LIMBS>)],
+    tables: &mut [PowerTable<LIMBS>],
+    modulus: UInt<LIMBS>,
+    r: UInt<LIMBS>,
+    mod_neg_inv: Limb,
+) -> UInt<LIMBS> {
+    let mut exponent_bits = 0;
+    for ((base, exponent), table) in bases_and_exponents.iter().zip(tables.iter_mut()) {
+        *table = compute_powers(*base, modulus, r, mod_neg_inv);
+        exponent_bits = core::cmp::max(exponent_bits, exponent.bits_vartime());
+    }
+
+    let mut z = r;
+    let mut window_num = (exponent_bits + WINDOW - 1) / WINDOW;
+    let top_window = window_num.wrapping_sub(1);
+
+    while window_num > 0 {
+        window_num -= 1;
+
+        if window_num != top_window {
+            z = square_window(z, modulus, mod_neg_inv);
+        }
+
+        for ((_, exponent), table) in bases_and_exponents.iter().zip(tables.iter()) {
+            let idx = window_index(exponent, window_num * WINDOW) as usize;
+            if idx != 0 {
+                z = mul_montgomery_form(&z, &table[idx], modulus, mod_neg_inv);
+            }
+        }
+    }
+
+    z
+}
+
+/// Fills `table` so that `table[(i << WINDOW) + k] = x^(k * 2^(WINDOW * i))`, in Montgomery form.
+///
+/// Panics if `table` does not hold exactly [`fixed_base_table_len`] residues.
+pub(crate) fn compute_fixed_base_powers<const LIMBS: usize>(
+    table: &mut [UInt<LIMBS>],
+    x: UInt<LIMBS>,
+    modulus: UInt<LIMBS>,
+    r: UInt<LIMBS>,
+    mod_neg_inv: Limb,
+) {
+    assert_eq!(
+        table.len(),
+        fixed_base_table_len::<LIMBS>(),
+        "the table must hold one power table per window of the exponent"
+    );
+
+    let mut base = x;
+    for powers in table.chunks_exact_mut(1 << WINDOW) {
+        powers.copy_from_slice(&compute_powers(base, modulus, r, mod_neg_inv));
+        base = square_window(base, modulus, mod_neg_inv);
+    }
+}
+
+/// Performs modular exponentiation with a table filled by [`compute_fixed_base_powers`].
+/// Each window of the exponent costs one multiplication and no squarings, and only the power tables for the windows of a `RHS_LIMBS`-limb exponent are used.
+///
+/// Table entries are selected by scanning a whole power table, so the exponent is not leaked.
+/// Fails to compile if the exponent has more limbs than the residue, as the table has no powers for its upper windows.
+pub(crate) fn pow_fixed_base_montgomery_form<const LIMBS: usize, const RHS_LIMBS: usize>(
+    table: &[UInt<LIMBS>],
+    exponent: &UInt<RHS_LIMBS>,
+    modulus: UInt<LIMBS>,
+    r: UInt<LIMBS>,
+    mod_neg_inv: Limb,
+) -> UInt<LIMBS> {
+    let () = FixedBaseExponent::<LIMBS, RHS_LIMBS>::ASSERT;
 
-        let mut product: UInt<LIMBS> = x1;
-        product = mul_montgomery_form(&product, &x2, modulus, mod_neg_inv);
+    let windows = RHS_LIMBS * Limb::BIT_SIZE / WINDOW;
+    let mut z = r;
+    for (i, powers) in table.chunks_exact(1 << WINDOW).take(windows).enumerate() {
+        let power = ct_lookup(powers, window_index(exponent, i * WINDOW));
+        z = mul_montgomery_form(&z, &power, modulus, mod_neg_inv);
+    }
 
-        let mut square = UInt::ct_select(x1, x2, overflow);
-        square = square_montgomery_form(&square, modulus, mod_neg_inv);
+    z
+}
 
-        x1 = UInt::<LIMBS>::ct_select(square, product, overflow);
-        x2 = UInt::<LIMBS>::ct_select(product, square, overflow);
+/// Checks at compile time that a fixed-base table for residues of `LIMBS` limbs covers exponents of `RHS_LIMBS` limbs.
+struct FixedBaseExponent<const LIMBS: usize, const RHS_LIMBS: usize>;
 
+impl<const LIMBS: usize, const RHS_LIMBS: usize> FixedBaseExponent<LIMBS, RHS_LIMBS> {
+    const ASSERT: () = assert!(
+        RHS_LIMBS <= LIMBS,
+        "the exponent must not have more limbs than the residue"
+    );
+}
+
+/// Returns the table `[x^0, x^1, ..., x^(2^WINDOW - 1)]`, in Montgomery form.
+const fn compute_powers<const LIMBS: usize>(
+    x: UInt<LIMBS>,
+    modulus: UInt<LIMBS>,
+    r: UInt<LIMBS>,
+    mod_neg_inv: Limb,
+) -> PowerTable<LIMBS> {
+    let mut powers = [r; 1 << WINDOW];
+    powers[1] = x;
+    let mut i = 2;
+    while i < powers.len() {
+        powers[i] = mul_montgomery_form(&powers[i - 1], &x, modulus, mod_neg_inv);
         i += 1;
     }
+    powers
+}
 
-    x1
+/// Returns the `WINDOW` bits of `exponent` starting at bit `bit`.
+/// Windows never straddle limbs, since `WINDOW` divides the limb size.
+pub(crate) const fn window_index<const RHS_LIMBS: usize>(
+    exponent: &UInt<RHS_LIMBS>,
+    bit: usize,
+) -> Word {
+    (exponent.limbs[bit / Limb::BIT_SIZE].0 >> (bit % Limb::BIT_SIZE)) & WINDOW_MASK
+}
+
+/// Squares `z` `WINDOW` times.
+const fn square_window<const LIMBS: usize>(
+    mut z: UInt<LIMBS>,
+    modulus: UInt<LIMBS>,
+    mod_neg_inv: Limb,
+) -> UInt<LIMBS> {
+    let mut i = 0;
+    while i < WINDOW {
+        z = square_montgomery_form(&z, modulus, mod_neg_inv);
+        i += 1;
+    }
+    z
+}
+
+/// Constant-time lookup of `table[idx]`, touching every entry of the table.
+pub(crate) const fn ct_lookup<const LIMBS: usize>(table: &[UInt<LIMBS>], idx: Word) -> UInt<LIMBS> {
+    let mut entry = table[0];
+    let mut i = 1;
+    while i < table.len() {
+        let choice = Limb::is_nonzero(Limb(i as Word ^ idx)) ^ Word::MAX;
+        entry = UInt::ct_select(entry, table[i], choice);
+        i += 1;
+    }
+    entry
 }
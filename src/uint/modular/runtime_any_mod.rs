@@ -0,0 +1,515 @@
+use core::ops::Neg;
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::{UInt, Word};
+
+use super::{
+    runtime_mod::{DynResidue, DynResidueParams},
+    AddResidue, GenericResidue, InvResidue, MulResidue, NegResidue, PowResidue, SubResidue,
+};
+
+/// The parameters for residues modulo any non-zero modulus provided at runtime.
+///
+/// The modulus `n` is split as `n = 2^k * m` with `m` odd. Arithmetic modulo `m` is done in Montgomery form, arithmetic modulo `2^k` by simply truncating, and the two parts are recombined using the Chinese Remainder Theorem.
+#[derive(Debug, Clone, Copy)]
+pub struct AnyDynResidueParams<const LIMBS: usize> {
+    // The full modulus
+    modulus: UInt<LIMBS>,
+    // The parameters for the odd part `m` of the modulus
+    odd_params: DynResidueParams<LIMBS>,
+    // The odd part `m` of the modulus
+    odd_modulus: UInt<LIMBS>,
+    // The exponent `k` of the power-of-two part of the modulus
+    k: usize,
+    // 2^k - 1, used to reduce modulo 2^k
+    mask: UInt<LIMBS>,
+    // m^-1 mod 2^k, used for the CRT recombination
+    odd_modulus_inv: UInt<LIMBS>,
+}
+
+impl<const LIMBS: usize> AnyDynResidueParams<LIMBS> {
+    /// Instantiates a new set of parameters representing the given `modulus`, which may be even.
+    ///
+    /// Panics if `modulus` is zero. The time pattern depends on the number of trailing zeros of `modulus`.
+    pub const fn new(modulus: UInt<LIMBS>) -> Self {
+        assert!(
+            modulus.ct_is_nonzero() == Word::MAX,
+            "modulus must be non-zero"
+        );
+
+        let mut k = 0;
+        while modulus.bit_vartime(k) == 0 {
+            k += 1;
+        }
+
+        let odd_modulus = modulus.shr_vartime(k);
+        let mask = UInt::ONE.shl_vartime(k).wrapping_sub(&UInt::ONE);
+        let odd_modulus_inv = odd_modulus.bitand(&mask).inv_mod2k(k);
+
+        Self {
+            modulus,
+            odd_params: DynResidueParams::new(odd_modulus),
+            odd_modulus,
+            k,
+            mask,
+            odd_modulus_inv,
+        }
+    }
+
+    /// Instantiates a new set of parameters representing the given `modulus`, checking that it is non-zero. Returns `None` otherwise.
+    pub fn new_checked(modulus: UInt<LIMBS>) -> CtOption<Self> {
+        let is_nonzero = modulus.ct_is_nonzero();
+        let params = Self::new(UInt::ct_select(UInt::ONE, modulus, is_nonzero));
+        CtOption::new(params, Choice::from((is_nonzero & 1) as u8))
+    }
+
+    /// Returns the modulus which was used to initialize these parameters.
+    pub const fn modulus(&self) -> &UInt<LIMBS> {
+        &self.modulus
+    }
+}
+
+impl<const LIMBS: usize> ConstantTimeEq for AnyDynResidueParams<LIMBS> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // All the other parameters are determined by the modulus
+        self.modulus.ct_eq(&other.modulus)
+    }
+}
+
+impl<const LIMBS: usize> PartialEq for AnyDynResidueParams<LIMBS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const LIMBS: usize> Eq for AnyDynResidueParams<LIMBS> {}
+
+impl<const LIMBS: usize> ConditionallySelectable for AnyDynResidueParams<LIMBS> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            modulus: UInt::conditional_select(&a.modulus, &b.modulus, choice),
+            odd_params: DynResidueParams::conditional_select(&a.odd_params, &b.odd_params, choice),
+            odd_modulus: UInt::conditional_select(&a.odd_modulus, &b.odd_modulus, choice),
+            k: u64::conditional_select(&(a.k as u64), &(b.k as u64), choice) as usize,
+            mask: UInt::conditional_select(&a.mask, &b.mask, choice),
+            odd_modulus_inv: UInt::conditional_select(
+                &a.odd_modulus_inv,
+                &b.odd_modulus_inv,
+                choice,
+            ),
+        }
+    }
+}
+
+/// A residue represented using `LIMBS` limbs. The modulus of this residue is set at runtime, and unlike for [`DynResidue`] it may be even.
+#[derive(Debug, Clone, Copy)]
+pub struct AnyDynResidue<const LIMBS: usize> {
+    // The residue modulo the odd part of the modulus
+    odd: DynResidue<LIMBS>,
+    // The residue modulo the power-of-two part of the modulus
+    even: UInt<LIMBS>,
+    residue_params: AnyDynResidueParams<LIMBS>,
+}
+
+impl<const LIMBS: usize> AnyDynResidue<LIMBS> {
+    /// Instantiates a new `AnyDynResidue` that represents zero.
+    pub const fn zero(residue_params: AnyDynResidueParams<LIMBS>) -> Self {
+        Self {
+            odd: DynResidue::zero(residue_params.odd_params),
+            even: UInt::ZERO,
+            residue_params,
+        }
+    }
+
+    /// Instantiates a new `AnyDynResidue` that represents 1.
+    pub const fn one(residue_params: AnyDynResidueParams<LIMBS>) -> Self {
+        Self {
+            odd: DynResidue::one(residue_params.odd_params),
+            even: UInt::ONE.bitand(&residue_params.mask),
+            residue_params,
+        }
+    }
+
+    /// Instantiates a new `AnyDynResidue` that represents this `integer` mod the modulus of `residue_params`.
+    pub const fn new(integer: UInt<LIMBS>, residue_params: AnyDynResidueParams<LIMBS>) -> Self {
+        Self {
+            odd: DynResidue::new(integer, residue_params.odd_params),
+            even: integer.bitand(&residue_params.mask),
+            residue_params,
+        }
+    }
+
+    /// Retrieves the integer currently encoded in this residue, guaranteed to be reduced.
+    pub const fn retrieve(&self) -> UInt<LIMBS> {
+        let params = &self.residue_params;
+
+        // x = a + m * ((b - a) * m^-1 mod 2^k), where x = a mod m and x = b mod 2^k
+        let a = self.odd.retrieve();
+        let t = self
+            .even
+            .wrapping_sub(&a)
+            .wrapping_mul(&params.odd_modulus_inv)
+            .bitand(&params.mask);
+        a.wrapping_add(&params.odd_modulus.wrapping_mul(&t))
+    }
+
+    /// Returns the parameter struct used to initialize this residue.
+    pub const fn params(&self) -> &AnyDynResidueParams<LIMBS> {
+        &self.residue_params
+    }
+
+    /// Performs modular exponentiation, taking `exponent_bits` bits of the exponent into account. Note that this value is leaked in the time pattern.
+    pub const fn pow_specific<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self {
+        let mask = self.residue_params.mask;
+
+        // Square-and-always-multiply modulo 2^k
+        let mut even = UInt::ONE.bitand(&mask);
+        let mut i = exponent_bits;
+        while i > 0 {
+            i -= 1;
+            even = even.wrapping_mul(&even).bitand(&mask);
+            let product = even.wrapping_mul(&self.even).bitand(&mask);
+            let bit = exponent.bit_vartime(i).wrapping_neg();
+            even = UInt::ct_select(even, product, bit);
+        }
+
+        Self {
+            odd: self.odd.pow_specific(exponent, exponent_bits),
+            even,
+            residue_params: self.residue_params,
+        }
+    }
+}
+
+impl<const LIMBS: usize> AddResidue for AnyDynResidue<LIMBS> {
+    fn add(&self, rhs: &Self) -> Self {
+        debug_assert_eq!(self.residue_params, rhs.residue_params);
+        Self {
+            odd: AddResidue::add(&self.odd, &rhs.odd),
+            even: self
+                .even
+                .wrapping_add(&rhs.even)
+                .bitand(&self.residue_params.mask),
+            residue_params: self.residue_params,
+        }
+    }
+}
+
+impl<const LIMBS: usize> SubResidue for AnyDynResidue<LIMBS> {
+    fn sub(&self, rhs: &Self) -> Self {
+        debug_assert_eq!(self.residue_params, rhs.residue_params);
+        Self {
+            odd: SubResidue::sub(&self.odd, &rhs.odd),
+            even: self
+                .even
+                .wrapping_sub(&rhs.even)
+                .bitand(&self.residue_params.mask),
+            residue_params: self.residue_params,
+        }
+    }
+}
+
+impl<const LIMBS: usize> NegResidue for AnyDynResidue<LIMBS> {
+    fn neg(&self) -> Self {
+        Self {
+            odd: NegResidue::neg(&self.odd),
+            even: UInt::ZERO
+                .wrapping_sub(&self.even)
+                .bitand(&self.residue_params.mask),
+            residue_params: self.residue_params,
+        }
+    }
+}
+
+impl<const LIMBS: usize> MulResidue for AnyDynResidue<LIMBS> {
+    fn mul(&self, rhs: &Self) -> Self {
+        debug_assert_eq!(self.residue_params, rhs.residue_params);
+        Self {
+            odd: MulResidue::mul(&self.odd, &rhs.odd),
+            even: self
+                .even
+                .wrapping_mul(&rhs.even)
+                .bitand(&self.residue_params.mask),
+            residue_params: self.residue_params,
+        }
+    }
+
+    fn square(&self) -> Self {
+        Self {
+            odd: MulResidue::square(&self.odd),
+            even: self
+                .even
+                .wrapping_mul(&self.even)
+                .bitand(&self.residue_params.mask),
+            residue_params: self.residue_params,
+        }
+    }
+}
+
+impl<const LIMBS: usize> PowResidue<LIMBS> for AnyDynResidue<LIMBS> {
+    fn pow_specific<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self {
+        self.pow_specific(exponent, exponent_bits)
+    }
+}
+
+impl<const LIMBS: usize> InvResidue for AnyDynResidue<LIMBS> {
+    fn inv(self) -> CtOption<Self> {
+        let params = self.residue_params;
+
+        let odd = InvResidue::inv(self.odd);
+
+        // Modulo 2^k, the residue is invertible iff it is odd (or k = 0)
+        let even_invertible = self.even.ct_is_odd() | Word::from(params.k == 0).wrapping_neg();
+        let even = self.even.inv_mod2k(params.k);
+
+        let value = Self {
+            odd: odd.unwrap_or(DynResidue::zero(params.odd_params)),
+            even,
+            residue_params: params,
+        };
+
+        CtOption::new(
+            value,
+            odd.is_some() & Choice::from((even_invertible & 1) as u8),
+        )
+    }
+}
+
+impl<const LIMBS: usize> GenericResidue<LIMBS> for AnyDynResidue<LIMBS> {
+    fn retrieve(&self) -> UInt<LIMBS> {
+        self.retrieve()
+    }
+}
+
+impl_residue_binary_op!(
+    [const LIMBS: usize] AnyDynResidue<LIMBS>,
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    AddResidue::add
+);
+
+impl_residue_binary_op!(
+    [const LIMBS: usize] AnyDynResidue<LIMBS>,
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    SubResidue::sub
+);
+
+impl_residue_binary_op!(
+    [const LIMBS: usize] AnyDynResidue<LIMBS>,
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    MulResidue::mul
+);
+
+/// Computes `lhs / rhs`, which is none if `rhs` is not invertible.
+fn div<const LIMBS: usize>(
+    lhs: &AnyDynResidue<LIMBS>,
+    rhs: &AnyDynResidue<LIMBS>,
+) -> CtOption<AnyDynResidue<LIMBS>> {
+    let inverse = InvResidue::inv(*rhs);
+    let quotient = MulResidue::mul(
+        lhs,
+        &inverse.unwrap_or(AnyDynResidue::zero(rhs.residue_params)),
+    );
+    CtOption::new(quotient, inverse.is_some())
+}
+
+impl_residue_binary_op!(
+    [const LIMBS: usize] AnyDynResidue<LIMBS>,
+    CtOption<AnyDynResidue<LIMBS>>,
+    Div,
+    div,
+    div
+);
+
+impl<const LIMBS: usize> Neg for AnyDynResidue<LIMBS> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        NegResidue::neg(&self)
+    }
+}
+
+impl<const LIMBS: usize> Neg for &AnyDynResidue<LIMBS> {
+    type Output = AnyDynResidue<LIMBS>;
+
+    fn neg(self) -> AnyDynResidue<LIMBS> {
+        NegResidue::neg(self)
+    }
+}
+
+impl<const LIMBS: usize> ConstantTimeEq for AnyDynResidue<LIMBS> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.odd.ct_eq(&other.odd)
+            & self.even.ct_eq(&other.even)
+            & self.residue_params.ct_eq(&other.residue_params)
+    }
+}
+
+impl<const LIMBS: usize> PartialEq for AnyDynResidue<LIMBS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const LIMBS: usize> Eq for AnyDynResidue<LIMBS> {}
+
+impl<const LIMBS: usize> ConditionallySelectable for AnyDynResidue<LIMBS> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            odd: DynResidue::conditional_select(&a.odd, &b.odd, choice),
+            even: UInt::conditional_select(&a.even, &b.even, choice),
+            residue_params: AnyDynResidueParams::conditional_select(
+                &a.residue_params,
+                &b.residue_params,
+                choice,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+    use super::{AnyDynResidue, AnyDynResidueParams};
+    use crate::{
+        modular::{InvResidue, PowResidue},
+        U256,
+    };
+
+    // 2^5 * an odd number
+    const MODULUS: U256 =
+        U256::from_be_hex("1f54f0d4bf5d10f9e0b79a0e8c4a1b3d6d2bc26bf2b5f8f3fa1c0a1d7e1f4e60");
+
+    #[test]
+    fn new_retrieve() {
+        let params = AnyDynResidueParams::new(MODULUS);
+        let x =
+            U256::from_be_hex("0c97e5b29c4fe0fd4d0b0fa0d6a7a6ec5ac4b78a4c33f3c0ffba14b1ac1b0f23");
+        assert_eq!(AnyDynResidue::new(x, params).retrieve(), x);
+
+        // Reduces integers larger than the modulus
+        let y = MODULUS.wrapping_add(&x);
+        assert_eq!(AnyDynResidue::new(y, params).retrieve(), x);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn arithmetic() {
+        let params = AnyDynResidueParams::new(MODULUS);
+        let x =
+            U256::from_be_hex("0c97e5b29c4fe0fd4d0b0fa0d6a7a6ec5ac4b78a4c33f3c0ffba14b1ac1b0f23");
+        let y =
+            U256::from_be_hex("15b7a2c8e6d9f0b4c3d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a1b0");
+        let x_mod = AnyDynResidue::new(x, params);
+        let y_mod = AnyDynResidue::new(y, params);
+
+        assert_eq!(
+            (x_mod + y_mod).retrieve(),
+            U256::from_be_hex("02fa97a6c3ccc0b830265782f4165385d38d98d11b4ee4c5ad54d06911ee6273")
+        );
+        assert_eq!(
+            (x_mod - y_mod).retrieve(),
+            U256::from_be_hex("163533be74d3014269efc7beb938fa52e1fbd6437d1902bc521f58fa4647bbd3")
+        );
+        assert_eq!(
+            (x_mod * y_mod).retrieve(),
+            U256::from_be_hex("0789e7b88bd5571f8c679a850c7d76e0fab6d05eaefab6ccf510fdc50a5e5310")
+        );
+        assert_eq!((-x_mod + x_mod).retrieve(), U256::ZERO);
+
+        let mut z_mod = x_mod;
+        z_mod *= &y_mod;
+        z_mod -= y_mod;
+        z_mod += &y_mod;
+        assert_eq!(z_mod, &x_mod * &y_mod);
+
+        let exponent = U256::from(65537u64);
+        assert_eq!(
+            x_mod.pow(&exponent).retrieve(),
+            U256::from_be_hex("0d0ba4c59f6cee30499026e71816f2e1ceb52bd3c7d1961318db1866c7db9d63")
+        );
+    }
+
+    #[test]
+    fn inversion() {
+        let params = AnyDynResidueParams::new(MODULUS);
+        let x =
+            U256::from_be_hex("0c97e5b29c4fe0fd4d0b0fa0d6a7a6ec5ac4b78a4c33f3c0ffba14b1ac1b0f23");
+        let x_mod = AnyDynResidue::new(x, params);
+
+        let inv = x_mod.inv().unwrap();
+        assert_eq!((x_mod * inv).retrieve(), U256::ONE);
+
+        // Even residues have no inverse modulo an even modulus
+        let two = AnyDynResidue::new(U256::from(2u64), params);
+        assert!(bool::from(two.inv().is_none()));
+        assert!(bool::from((x_mod / two).is_none()));
+        assert_eq!((two / x_mod).unwrap() * x_mod, two);
+
+        // Nor do multiples of the odd part of the modulus
+        let odd_part = AnyDynResidue::new(MODULUS.shr_vartime(5), params);
+        assert!(bool::from(odd_part.inv().is_none()));
+    }
+
+    #[test]
+    fn power_of_two_modulus() {
+        let params = AnyDynResidueParams::new(U256::ONE.shl_vartime(100));
+        let x =
+            U256::from_be_hex("000000000000000000000000000000000000000fedcba9876543210fedcba987");
+        let x_mod = AnyDynResidue::new(x, params);
+
+        assert_eq!(x_mod.retrieve(), x);
+        assert_eq!((x_mod * x_mod.inv().unwrap()).retrieve(), U256::ONE);
+    }
+
+    #[test]
+    fn constant_time_traits() {
+        let params = AnyDynResidueParams::new(MODULUS);
+        let other_params = AnyDynResidueParams::new(MODULUS.shr_vartime(1));
+        assert!(bool::from(params.ct_eq(&params)));
+        assert!(!bool::from(params.ct_eq(&other_params)));
+        assert_eq!(
+            AnyDynResidueParams::conditional_select(&params, &other_params, Choice::from(1)),
+            other_params
+        );
+
+        let x =
+            U256::from_be_hex("0c97e5b29c4fe0fd4d0b0fa0d6a7a6ec5ac4b78a4c33f3c0ffba14b1ac1b0f23");
+        let x_mod = AnyDynResidue::new(x, params);
+        let one = AnyDynResidue::one(params);
+        assert!(bool::from(x_mod.ct_eq(&x_mod)));
+        assert!(!bool::from(x_mod.ct_eq(&one)));
+        assert!(!bool::from(one.ct_eq(&AnyDynResidue::one(other_params))));
+        assert_eq!(
+            AnyDynResidue::conditional_select(&x_mod, &one, Choice::from(0)),
+            x_mod
+        );
+        assert_eq!(
+            AnyDynResidue::conditional_select(&x_mod, &one, Choice::from(1)),
+            one
+        );
+
+        assert_eq!(one.retrieve(), U256::ONE);
+        assert_eq!(AnyDynResidue::zero(params).retrieve(), U256::ZERO);
+        assert_eq!(-&x_mod, -x_mod);
+        assert_eq!(x_mod.params().modulus(), &MODULUS);
+    }
+}
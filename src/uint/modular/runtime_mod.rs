@@ -1,18 +1,42 @@
-use crate::{Limb, UInt, Word};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
-use super::{reduction::montgomery_reduction, GenericResidue};
+use crate::{JacobiSymbol, Limb, UInt, Word};
+
+use super::{
+    constant_mod::{Residue, ResidueParams},
+    reduction::montgomery_reduction,
+    GenericResidue,
+};
 
 /// Additions between residues with a modulus set at runtime
 mod runtime_add;
+/// Encodings of residues with a modulus set at runtime
+#[cfg(any(feature = "serde", all(feature = "der", feature = "generic-array")))]
+mod runtime_encoding;
 /// Multiplicative inverses of residues with a modulus set at runtime
 mod runtime_inv;
 /// Multiplications between residues with a modulus set at runtime
 mod runtime_mul;
+/// Negations of residues with a modulus set at runtime
+mod runtime_neg;
 /// Exponentiation of residues with a modulus set at runtime
 mod runtime_pow;
+/// Random residues with a modulus set at runtime
+#[cfg(feature = "rand_core")]
+mod runtime_rand;
+/// Residues borrowing their parameters, with a modulus set at runtime
+mod runtime_ref;
+/// Square roots of residues with a modulus set at runtime
+mod runtime_sqrt;
+/// Subtractions between residues with a modulus set at runtime
+mod runtime_sub;
+
+pub use self::{
+    runtime_pow::DynFixedBaseTable, runtime_ref::DynResidueRef, runtime_sqrt::DynSqrtParams,
+};
 
 /// The parameters to efficiently go to and from the Montgomery form for a modulus provided at runtime.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy)]
 pub struct DynResidueParams<const LIMBS: usize> {
     // The constant modulus
     modulus: UInt<LIMBS>,
@@ -29,7 +53,9 @@ pub struct DynResidueParams<const LIMBS: usize> {
 
 impl<const LIMBS: usize> DynResidueParams<LIMBS> {
     /// Instantiates a new set of `ResidueParams` representing the given `modulus`.
-    pub fn new(modulus: UInt<LIMBS>) -> Self {
+    ///
+    /// The modulus must be odd, otherwise the resulting parameters are meaningless. Use [`DynResidueParams::new_checked`] or [`DynResidueParams::try_new`] for moduli that are not known to be valid.
+    pub const fn new(modulus: UInt<LIMBS>) -> Self {
         let r = UInt::MAX.ct_reduce(&modulus).0.wrapping_add(&UInt::ONE);
         let r2 = UInt::ct_reduce_wide(r.square_wide(), &modulus).0;
         let mod_neg_inv =
@@ -44,16 +70,111 @@ impl<const LIMBS: usize> DynResidueParams<LIMBS> {
             mod_neg_inv,
         }
     }
+
+    /// Instantiates a new set of `ResidueParams` representing the given `modulus`, checking that the modulus is odd (and thus non-zero). Returns `None` otherwise.
+    ///
+    /// This is constant-time with respect to the validity of the modulus.
+    pub fn new_checked(modulus: UInt<LIMBS>) -> CtOption<Self> {
+        let is_odd = modulus.ct_is_odd();
+        // Substitute a valid modulus so the computation of the parameters is always well-defined
+        let params = Self::new(UInt::ct_select(UInt::ONE, modulus, is_odd));
+        CtOption::new(params, Choice::from((is_odd & 1) as u8))
+    }
+
+    /// Returns the parameters of the constant modulus `MOD`, without recomputing them.
+    pub const fn from_params<MOD: ResidueParams<LIMBS>>() -> Self {
+        Self {
+            modulus: MOD::MODULUS,
+            r: MOD::R,
+            r2: MOD::R2,
+            r3: MOD::R3,
+            mod_neg_inv: MOD::MOD_NEG_INV,
+        }
+    }
+
+    /// Returns the modulus.
+    pub const fn modulus(&self) -> &UInt<LIMBS> {
+        &self.modulus
+    }
+
+    /// Returns `R = 2^(LIMBS * Word::BITS) mod modulus`, the Montgomery form of 1.
+    pub const fn r(&self) -> &UInt<LIMBS> {
+        &self.r
+    }
+
+    /// Returns `R^2 mod modulus`, used to move into Montgomery form.
+    pub const fn r2(&self) -> &UInt<LIMBS> {
+        &self.r2
+    }
+
+    /// Returns the lowest limb of `-(modulus^-1) mod R`, used in Montgomery reduction.
+    pub const fn mod_neg_inv(&self) -> Limb {
+        self.mod_neg_inv
+    }
+
+    /// Const-friendly version of [`DynResidueParams::new_checked`]: returns `None` if `modulus` is even (or zero).
+    ///
+    /// This branches on the validity of the modulus.
+    pub const fn try_new(modulus: UInt<LIMBS>) -> Option<Self> {
+        if modulus.ct_is_odd() == Word::MAX {
+            Some(Self::new(modulus))
+        } else {
+            None
+        }
+    }
 }
 
 /// A residue represented using `LIMBS` limbs. The odd modulus of this residue is set at runtime.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy)]
 pub struct DynResidue<const LIMBS: usize> {
     montgomery_form: UInt<LIMBS>,
     residue_params: DynResidueParams<LIMBS>,
 }
 
+impl<const LIMBS: usize> ConstantTimeEq for DynResidueParams<LIMBS> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // All the other parameters are determined by the modulus
+        self.modulus.ct_eq(&other.modulus)
+    }
+}
+
+impl<const LIMBS: usize> PartialEq for DynResidueParams<LIMBS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const LIMBS: usize> Eq for DynResidueParams<LIMBS> {}
+
+impl<const LIMBS: usize> ConditionallySelectable for DynResidueParams<LIMBS> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            modulus: UInt::conditional_select(&a.modulus, &b.modulus, choice),
+            r: UInt::conditional_select(&a.r, &b.r, choice),
+            r2: UInt::conditional_select(&a.r2, &b.r2, choice),
+            r3: UInt::conditional_select(&a.r3, &b.r3, choice),
+            mod_neg_inv: Limb::conditional_select(&a.mod_neg_inv, &b.mod_neg_inv, choice),
+        }
+    }
+}
+
 impl<const LIMBS: usize> DynResidue<LIMBS> {
+    /// Instantiates the residue representing 0 with the given parameters.
+    pub const fn zero(residue_params: DynResidueParams<LIMBS>) -> Self {
+        Self {
+            montgomery_form: UInt::ZERO,
+            residue_params,
+        }
+    }
+
+    /// Instantiates the residue representing 1 with the given parameters.
+    pub const fn one(residue_params: DynResidueParams<LIMBS>) -> Self {
+        Self {
+            montgomery_form: residue_params.r,
+            residue_params,
+        }
+    }
+
     /// Instantiates a new `Residue` that represents this `integer` mod `MOD`.
     pub const fn new(integer: UInt<LIMBS>, residue_params: DynResidueParams<LIMBS>) -> Self {
         let mut modular_integer = Self {
@@ -76,6 +197,98 @@ impl<const LIMBS: usize> DynResidue<LIMBS> {
             self.residue_params.mod_neg_inv,
         )
     }
+
+    /// Computes the Legendre symbol of this residue in constant time, assuming the modulus is an odd prime.
+    pub const fn legendre(&self) -> JacobiSymbol {
+        self.retrieve().jacobi(&self.residue_params.modulus)
+    }
+
+    /// Instantiates a new residue from its Montgomery form `x * R mod modulus`, where `R = 2^(LIMBS * Word::BITS)`.
+    ///
+    /// The Montgomery form must be reduced, i.e. less than the modulus; this is not checked.
+    pub const fn from_montgomery(
+        montgomery_form: UInt<LIMBS>,
+        residue_params: DynResidueParams<LIMBS>,
+    ) -> Self {
+        Self {
+            montgomery_form,
+            residue_params,
+        }
+    }
+
+    /// Returns the Montgomery form `x * R mod modulus` of this residue, where `R = 2^(LIMBS * Word::BITS)`.
+    pub const fn as_montgomery(&self) -> &UInt<LIMBS> {
+        &self.montgomery_form
+    }
+
+    /// Returns the parameters of this residue.
+    pub const fn params(&self) -> &DynResidueParams<LIMBS> {
+        &self.residue_params
+    }
+}
+
+impl<MOD: ResidueParams<LIMBS>, const LIMBS: usize> From<Residue<MOD, LIMBS>>
+    for DynResidue<LIMBS>
+{
+    fn from(residue: Residue<MOD, LIMBS>) -> Self {
+        Self {
+            montgomery_form: *residue.as_montgomery(),
+            residue_params: DynResidueParams::from_params::<MOD>(),
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<const LIMBS: usize> zeroize::Zeroize for DynResidueParams<LIMBS> {
+    fn zeroize(&mut self) {
+        self.modulus.zeroize();
+        self.r.zeroize();
+        self.r2.zeroize();
+        self.r3.zeroize();
+        self.mod_neg_inv.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<const LIMBS: usize> zeroize::Zeroize for DynResidue<LIMBS> {
+    fn zeroize(&mut self) {
+        self.montgomery_form.zeroize();
+        self.residue_params.zeroize();
+    }
+}
+
+impl<const LIMBS: usize> ConstantTimeEq for DynResidue<LIMBS> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.montgomery_form.ct_eq(&other.montgomery_form)
+            & self.residue_params.ct_eq(&other.residue_params)
+    }
+}
+
+impl<const LIMBS: usize> PartialEq for DynResidue<LIMBS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const LIMBS: usize> Eq for DynResidue<LIMBS> {}
+
+impl<const LIMBS: usize> ConditionallySelectable for DynResidue<LIMBS> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            montgomery_form: UInt::conditional_select(
+                &a.montgomery_form,
+                &b.montgomery_form,
+                choice,
+            ),
+            residue_params: DynResidueParams::conditional_select(
+                &a.residue_params,
+                &b.residue_params,
+                choice,
+            ),
+        }
+    }
 }
 
 impl<const LIMBS: usize> GenericResidue<LIMBS> for DynResidue<LIMBS> {
@@ -83,3 +296,36 @@ impl<const LIMBS: usize> GenericResidue<LIMBS> for DynResidue<LIMBS> {
         self.retrieve()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{modular::runtime_mod::DynResidueParams, U256};
+
+    #[test]
+    fn new_checked() {
+        let modulus =
+            U256::from_be_hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+        let params = DynResidueParams::new_checked(modulus).unwrap();
+        assert_eq!(params, DynResidueParams::new(modulus));
+        assert_eq!(DynResidueParams::try_new(modulus), Some(params));
+
+        for invalid in [
+            U256::ZERO,
+            U256::from(2u64),
+            modulus.wrapping_add(&U256::ONE),
+        ] {
+            assert!(bool::from(DynResidueParams::new_checked(invalid).is_none()));
+            assert_eq!(DynResidueParams::try_new(invalid), None);
+        }
+    }
+
+    #[test]
+    fn try_new_const() {
+        const PARAMS: Option<DynResidueParams<{ U256::LIMBS }>> =
+            DynResidueParams::try_new(U256::from_u64(105));
+        const INVALID: Option<DynResidueParams<{ U256::LIMBS }>> =
+            DynResidueParams::try_new(U256::from_u64(104));
+        assert_eq!(PARAMS, Some(DynResidueParams::new(U256::from_u64(105))));
+        assert!(INVALID.is_none());
+    }
+}
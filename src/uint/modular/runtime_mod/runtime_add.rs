@@ -1,11 +1,11 @@
-use core::ops::{Add, AddAssign};
+use core::{borrow::Borrow, ops::AddAssign};
 
 use crate::{
     modular::{add::add_montgomery_form, AddResidue},
     UInt,
 };
 
-use super::DynResidue;
+use super::{DynResidue, DynResidueParams};
 
 impl<const LIMBS: usize> AddResidue for DynResidue<LIMBS> {
     fn add(&self, rhs: &Self) -> Self {
@@ -21,32 +21,42 @@ impl<const LIMBS: usize> AddResidue for DynResidue<LIMBS> {
     }
 }
 
-impl<const LIMBS: usize> AddAssign for DynResidue<LIMBS> {
-    fn add_assign(&mut self, rhs: Self) {
-        self.montgomery_form = add_montgomery_form(
-            &self.montgomery_form,
-            &rhs.montgomery_form,
-            &self.residue_params.modulus,
-        );
+impl_residue_binary_op!(
+    [const LIMBS: usize] DynResidue<LIMBS>,
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    AddResidue::add
+);
+
+impl<const LIMBS: usize> AddAssign<&UInt<LIMBS>> for DynResidue<LIMBS> {
+    fn add_assign(&mut self, rhs: &UInt<LIMBS>) {
+        *self += DynResidue::new(*rhs, self.residue_params);
     }
 }
 
 impl<const LIMBS: usize> AddAssign<UInt<LIMBS>> for DynResidue<LIMBS> {
     fn add_assign(&mut self, rhs: UInt<LIMBS>) {
-        self.montgomery_form = add_montgomery_form(
-            &self.montgomery_form,
-            &DynResidue::new(rhs, self.residue_params).montgomery_form,
-            &self.residue_params.modulus,
-        );
+        *self += DynResidue::new(rhs, self.residue_params);
     }
 }
 
-impl<const LIMBS: usize> Add for DynResidue<LIMBS> {
-    type Output = DynResidue<LIMBS>;
-
-    fn add(mut self, rhs: Self) -> Self::Output {
-        self += rhs;
-        self
+impl<const LIMBS: usize> DynResidue<LIMBS> {
+    /// Computes the sum of all `residues`, which must have the given `residue_params`.
+    /// The sum of no residues is zero.
+    ///
+    /// Unlike [`Residue`](crate::modular::constant_mod::Residue), this type does not implement [`core::iter::Sum`]: the sum of an empty iterator would have no modulus to be reduced by.
+    pub fn sum<I>(residues: I, residue_params: &DynResidueParams<LIMBS>) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Self>,
+    {
+        residues
+            .into_iter()
+            .fold(Self::zero(*residue_params), |acc, x| {
+                AddResidue::add(&acc, x.borrow())
+            })
     }
 }
 
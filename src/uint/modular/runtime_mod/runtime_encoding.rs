@@ -0,0 +1,247 @@
+//! Support for encoding [`DynResidue`]s and [`DynResidueParams`] in canonical (non-Montgomery) form.
+//!
+//! Parameters are encoded as their modulus, and a residue as the pair of its canonical value and its modulus.
+//! Decoding parameters only derives their Montgomery constants, which costs the same for every modulus of a given size, so untrusted moduli cannot make it slower; the constants for square roots are left to [`DynSqrtParams`](super::DynSqrtParams).
+
+use super::{DynResidue, DynResidueParams};
+use crate::UInt;
+
+#[cfg(feature = "serde")]
+use crate::Encoding;
+#[cfg(feature = "serde")]
+use serdect::serde::{
+    de::{Error, Unexpected},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+#[cfg(all(feature = "der", feature = "generic-array"))]
+use crate::ArrayEncoding;
+#[cfg(all(feature = "der", feature = "generic-array"))]
+use ::der::{DecodeValue, EncodeValue, FixedTag, Length, Reader, Sequence, Tag};
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, const LIMBS: usize> Deserialize<'de> for DynResidueParams<LIMBS>
+where
+    UInt<LIMBS>: Encoding,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let modulus = UInt::<LIMBS>::deserialize(deserializer)?;
+
+        Option::from(Self::new_checked(modulus)).ok_or_else(|| {
+            D::Error::invalid_value(Unexpected::Other("even modulus"), &"an odd modulus")
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<const LIMBS: usize> Serialize for DynResidueParams<LIMBS>
+where
+    UInt<LIMBS>: Encoding,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.modulus.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, const LIMBS: usize> Deserialize<'de> for DynResidue<LIMBS>
+where
+    UInt<LIMBS>: Encoding,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (value, residue_params) =
+            <(UInt<LIMBS>, DynResidueParams<LIMBS>)>::deserialize(deserializer)?;
+
+        if value < residue_params.modulus {
+            Ok(Self::new(value, residue_params))
+        } else {
+            Err(D::Error::invalid_value(
+                Unexpected::Other("unreduced residue"),
+                &"an integer less than the modulus",
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<const LIMBS: usize> Serialize for DynResidue<LIMBS>
+where
+    UInt<LIMBS>: Encoding,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self.retrieve(), self.residue_params).serialize(serializer)
+    }
+}
+
+#[cfg(all(feature = "der", feature = "generic-array"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+impl<'a, const LIMBS: usize> DecodeValue<'a> for DynResidueParams<LIMBS>
+where
+    UInt<LIMBS>: ArrayEncoding,
+{
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: der::Header) -> der::Result<Self> {
+        let modulus = UInt::<LIMBS>::decode_value(reader, header)?;
+        Self::try_new(modulus).ok_or_else(|| Tag::Integer.value_error())
+    }
+}
+
+#[cfg(all(feature = "der", feature = "generic-array"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+impl<const LIMBS: usize> EncodeValue for DynResidueParams<LIMBS>
+where
+    UInt<LIMBS>: ArrayEncoding,
+{
+    fn value_len(&self) -> der::Result<Length> {
+        self.modulus.value_len()
+    }
+
+    fn encode_value(&self, encoder: &mut dyn der::Writer) -> der::Result<()> {
+        self.modulus.encode_value(encoder)
+    }
+}
+
+#[cfg(all(feature = "der", feature = "generic-array"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+impl<const LIMBS: usize> FixedTag for DynResidueParams<LIMBS>
+where
+    UInt<LIMBS>: ArrayEncoding,
+{
+    const TAG: Tag = Tag::Integer;
+}
+
+#[cfg(all(feature = "der", feature = "generic-array"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+impl<'a, const LIMBS: usize> DecodeValue<'a> for DynResidue<LIMBS>
+where
+    UInt<LIMBS>: ArrayEncoding,
+{
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: der::Header) -> der::Result<Self> {
+        reader.read_nested(header.length, |reader| {
+            let value: UInt<LIMBS> = reader.decode()?;
+            let residue_params: DynResidueParams<LIMBS> = reader.decode()?;
+
+            if value < residue_params.modulus {
+                Ok(Self::new(value, residue_params))
+            } else {
+                Err(Tag::Integer.value_error())
+            }
+        })
+    }
+}
+
+#[cfg(all(feature = "der", feature = "generic-array"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+impl<'a, const LIMBS: usize> Sequence<'a> for DynResidue<LIMBS>
+where
+    UInt<LIMBS>: ArrayEncoding,
+{
+    fn fields<F, T>(&self, f: F) -> der::Result<T>
+    where
+        F: FnOnce(&[&dyn der::Encode]) -> der::Result<T>,
+    {
+        let value = self.retrieve();
+        f(&[&value, &self.residue_params])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        modular::runtime_mod::{DynResidue, DynResidueParams},
+        U256,
+    };
+
+    const MODULUS: U256 =
+        U256::from_be_hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let params = DynResidueParams::new(MODULUS);
+        let x = DynResidue::new(
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56"),
+            params,
+        );
+
+        let serialized = bincode::serialize(&x).unwrap();
+        let deserialized: DynResidue<{ U256::LIMBS }> = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(x, deserialized);
+
+        let serialized = bincode::serialize(&(MODULUS, MODULUS)).unwrap();
+        assert!(bincode::deserialize::<DynResidue<{ U256::LIMBS }>>(&serialized).is_err());
+
+        let serialized = bincode::serialize(&MODULUS.wrapping_add(&U256::ONE)).unwrap();
+        assert!(bincode::deserialize::<DynResidueParams<{ U256::LIMBS }>>(&serialized).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "der", feature = "generic-array"))]
+    fn der() {
+        use der::{Decode, Encode};
+
+        let params = DynResidueParams::new(MODULUS);
+        let x = DynResidue::new(
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56"),
+            params,
+        );
+
+        let mut buffer = [0u8; 128];
+        let encoded = x.encode_to_slice(&mut buffer).expect("encoding failed");
+        assert_eq!(DynResidue::from_der(encoded), Ok(x));
+
+        let mut buffer = [0u8; 64];
+        let encoded = params
+            .encode_to_slice(&mut buffer)
+            .expect("encoding failed");
+        assert_eq!(DynResidueParams::from_der(encoded), Ok(params));
+
+        let mut buffer = [0u8; 64];
+        let even = MODULUS.wrapping_add(&U256::ONE);
+        let encoded = even.encode_to_slice(&mut buffer).expect("encoding failed");
+        assert!(DynResidueParams::<{ U256::LIMBS }>::from_der(encoded).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "der", feature = "generic-array"))]
+    fn der_decoding_cost() {
+        use core::mem::size_of;
+        use der::{Decode, Encode};
+
+        use crate::Limb;
+
+        // Decoding derives nothing but the Montgomery constants, whose cost does not depend on the modulus
+        assert_eq!(
+            size_of::<DynResidueParams<{ U256::LIMBS }>>(),
+            4 * size_of::<U256>() + size_of::<Limb>()
+        );
+
+        // Including for moduli = 1 mod 8, for which square roots need a search for a non-residue
+        assert_eq!(MODULUS.limbs[0].0 & 7, 1);
+        let mut buffer = [0u8; 64];
+        let encoded = MODULUS
+            .encode_to_slice(&mut buffer)
+            .expect("encoding failed");
+        let decoded =
+            DynResidueParams::<{ U256::LIMBS }>::from_der(encoded).expect("decoding failed");
+        let expected = DynResidueParams::new(MODULUS);
+        assert_eq!(decoded.r(), expected.r());
+        assert_eq!(decoded.r2(), expected.r2());
+        assert_eq!(decoded.mod_neg_inv(), expected.mod_neg_inv());
+    }
+}
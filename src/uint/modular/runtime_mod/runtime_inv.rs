@@ -1,8 +1,8 @@
 use subtle::{Choice, CtOption};
 
 use crate::{
-    modular::{inv::inv_montgomery_form, InvResidue},
-    Word,
+    modular::{inv::inv_montgomery_form, mul::mul_montgomery_form, InvResidue, MulResidue},
+    UInt, Word,
 };
 
 use super::DynResidue;
@@ -24,3 +24,134 @@ impl<const LIMBS: usize> InvResidue for DynResidue<LIMBS> {
         CtOption::new(value, Choice::from((error == Word::MAX) as u8))
     }
 }
+
+impl<const LIMBS: usize> DynResidue<LIMBS> {
+    /// Inverts all `elements` in place using Montgomery's trick, which costs a single inversion and about `3n` multiplications.
+    /// All elements must share the same `residue_params`.
+    ///
+    /// Zero elements are left as zero. Returns a falsy `Choice` if any element was zero, or if the product of the non-zero elements was not invertible (in which case the non-zero elements are left in an unspecified state).
+    pub fn batch_invert_array<const N: usize>(elements: &mut [Self; N]) -> Choice {
+        let mut scratch = [UInt::ZERO; N];
+        Self::batch_invert_with_scratch(elements, &mut scratch)
+    }
+
+    /// Inverts all `elements` in place using Montgomery's trick, which costs a single inversion and about `3n` multiplications.
+    /// All elements must share the same `residue_params`.
+    ///
+    /// Zero elements are left as zero. Returns a falsy `Choice` if any element was zero, or if the product of the non-zero elements was not invertible (in which case the non-zero elements are left in an unspecified state).
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn batch_invert(elements: &mut [Self]) -> Choice {
+        let mut scratch = alloc::vec![UInt::ZERO; elements.len()];
+        Self::batch_invert_with_scratch(elements, &mut scratch)
+    }
+
+    /// Inverts all `elements` in place using Montgomery's trick, which costs a single inversion and about `3n` multiplications.
+    /// All elements must share the same `residue_params`.
+    ///
+    /// Instead of allocating, this uses the caller-provided `scratch` space, which must have the same length as `elements`; its contents are overwritten. Panics otherwise.
+    ///
+    /// Zero elements are left as zero. Returns a falsy `Choice` if any element was zero, or if the product of the non-zero elements was not invertible (in which case the non-zero elements are left in an unspecified state).
+    pub fn batch_invert_with_scratch(elements: &mut [Self], scratch: &mut [UInt<LIMBS>]) -> Choice {
+        assert_eq!(
+            elements.len(),
+            scratch.len(),
+            "the scratch space must have the same length as the elements"
+        );
+
+        let params = match elements.first() {
+            Some(element) => element.residue_params,
+            None => return Choice::from(1),
+        };
+
+        // Accumulate the prefix products, replacing zeros by one
+        let mut all_nonzero = Word::MAX;
+        let mut acc = params.r;
+        for (element, prefix) in elements.iter().zip(scratch.iter_mut()) {
+            debug_assert_eq!(element.residue_params, params);
+            *prefix = acc;
+            let nonzero = element.montgomery_form.ct_is_nonzero();
+            all_nonzero &= nonzero;
+            let factor = UInt::ct_select(params.r, element.montgomery_form, nonzero);
+            acc = mul_montgomery_form(&acc, &factor, params.modulus, params.mod_neg_inv);
+        }
+
+        let (mut acc, invertible) =
+            inv_montgomery_form(acc, params.modulus, &params.r3, params.mod_neg_inv);
+
+        // Walk back, peeling off one element at a time
+        for (element, prefix) in elements.iter_mut().zip(scratch.iter()).rev() {
+            let nonzero = element.montgomery_form.ct_is_nonzero();
+            let inverse = mul_montgomery_form(&acc, prefix, params.modulus, params.mod_neg_inv);
+            let factor = UInt::ct_select(params.r, element.montgomery_form, nonzero);
+            acc = mul_montgomery_form(&acc, &factor, params.modulus, params.mod_neg_inv);
+            element.montgomery_form = UInt::ct_select(element.montgomery_form, inverse, nonzero);
+        }
+
+        Choice::from((all_nonzero & invertible == Word::MAX) as u8)
+    }
+}
+
+/// Computes `lhs / rhs`, which is none if `rhs` is not invertible.
+fn div<const LIMBS: usize>(
+    lhs: &DynResidue<LIMBS>,
+    rhs: &DynResidue<LIMBS>,
+) -> CtOption<DynResidue<LIMBS>> {
+    let inverse = InvResidue::inv(*rhs);
+    let zero = DynResidue::zero(rhs.residue_params);
+    let quotient = MulResidue::mul(lhs, &inverse.unwrap_or(zero));
+    CtOption::new(quotient, inverse.is_some())
+}
+
+impl_residue_binary_op!(
+    [const LIMBS: usize] DynResidue<LIMBS>,
+    CtOption<DynResidue<LIMBS>>,
+    Div,
+    div,
+    div
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        modular::{
+            runtime_mod::{DynResidue, DynResidueParams},
+            InvResidue, MulResidue,
+        },
+        U256,
+    };
+
+    #[test]
+    fn test_batch_invert() {
+        let params = DynResidueParams::new(U256::from_be_hex(
+            "15477BCCEFE197328255BFA79A1217899016D927EF460F4FF404029D24FA4409",
+        ));
+
+        let x =
+            U256::from_be_hex("77117F1273373C26C700D076B3F780074D03339F56DD0EFB60E7F58441FD3685");
+        let x_mod = DynResidue::new(x, params);
+        let zero_mod = DynResidue::new(U256::ZERO, params);
+
+        let mut elements = [x_mod, x_mod.square()];
+        assert!(bool::from(DynResidue::batch_invert_array(&mut elements)));
+        assert_eq!(
+            elements,
+            [x_mod.inv().unwrap(), x_mod.square().inv().unwrap()]
+        );
+
+        let mut elements = [zero_mod, x_mod];
+        assert!(!bool::from(DynResidue::batch_invert_array(&mut elements)));
+        assert_eq!(elements, [zero_mod, x_mod.inv().unwrap()]);
+
+        let mut elements = [x_mod, x_mod.square()];
+        let mut scratch = [U256::ZERO; 2];
+        assert!(bool::from(DynResidue::batch_invert_with_scratch(
+            &mut elements,
+            &mut scratch
+        )));
+        assert_eq!(
+            elements,
+            [x_mod.inv().unwrap(), x_mod.square().inv().unwrap()]
+        );
+    }
+}
@@ -1,11 +1,12 @@
-use core::ops::{Mul, MulAssign};
+use core::borrow::Borrow;
 
 use crate::modular::{
+    accumulator::WideAccumulator,
     mul::{mul_montgomery_form, square_montgomery_form},
     MulResidue,
 };
 
-use super::DynResidue;
+use super::{DynResidue, DynResidueParams};
 
 impl<const LIMBS: usize> MulResidue for DynResidue<LIMBS> {
     fn mul(&self, rhs: &Self) -> Self {
@@ -33,23 +34,54 @@ impl<const LIMBS: usize> MulResidue for DynResidue<LIMBS> {
     }
 }
 
-impl<const LIMBS: usize> MulAssign for DynResidue<LIMBS> {
-    fn mul_assign(&mut self, rhs: Self) {
-        debug_assert_eq!(self.residue_params, rhs.residue_params);
-        self.montgomery_form = mul_montgomery_form(
-            &self.montgomery_form,
-            &rhs.montgomery_form,
-            self.residue_params.modulus,
-            self.residue_params.mod_neg_inv,
-        );
+impl<const LIMBS: usize> DynResidue<LIMBS> {
+    /// Computes the product of all `residues`, which must have the given `residue_params`.
+    /// The product of no residues is one.
+    ///
+    /// Unlike [`Residue`](crate::modular::constant_mod::Residue), this type does not implement [`core::iter::Product`]: the product of an empty iterator would have no modulus to be reduced by.
+    pub fn product<I>(residues: I, residue_params: &DynResidueParams<LIMBS>) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Self>,
+    {
+        residues
+            .into_iter()
+            .fold(Self::one(*residue_params), |acc, x| {
+                MulResidue::mul(&acc, x.borrow())
+            })
     }
-}
 
-impl<const LIMBS: usize> Mul for DynResidue<LIMBS> {
-    type Output = DynResidue<LIMBS>;
+    /// Computes the sum of the products `a[i] * b[i]`, with a single reduction at the end.
+    /// All residues must have the given `residue_params`, and the sum of no products is zero.
+    ///
+    /// Panics if `a` and `b` have different lengths.
+    pub fn sum_of_products(
+        a: &[Self],
+        b: &[Self],
+        residue_params: &DynResidueParams<LIMBS>,
+    ) -> Self {
+        assert_eq!(a.len(), b.len(), "the slices must have the same length");
+
+        let mut accumulator =
+            WideAccumulator::new(residue_params.modulus, residue_params.mod_neg_inv);
+        for (a, b) in a.iter().zip(b) {
+            debug_assert_eq!(&a.residue_params, residue_params);
+            debug_assert_eq!(&b.residue_params, residue_params);
+            accumulator.add_product(&a.montgomery_form, &b.montgomery_form);
+        }
 
-    fn mul(mut self, rhs: Self) -> Self::Output {
-        self *= rhs;
-        self
+        Self {
+            montgomery_form: accumulator.reduce(),
+            residue_params: *residue_params,
+        }
     }
 }
+
+impl_residue_binary_op!(
+    [const LIMBS: usize] DynResidue<LIMBS>,
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    MulResidue::mul
+);
@@ -1,19 +1,86 @@
 use crate::{
-    modular::{pow::pow_montgomery_form, PowResidue},
+    modular::{
+        pow::{
+            compute_fixed_base_powers, fixed_base_table_len, multi_exponentiate_chunked,
+            multi_exponentiate_montgomery_form, multi_exponentiate_montgomery_form_vartime,
+            pow_fixed_base_montgomery_form, pow_montgomery_form, pow_montgomery_form_vartime,
+            MultiExponentiation,
+        },
+        PowResidue,
+    },
     UInt,
 };
 
-use super::DynResidue;
+use super::{DynResidue, DynResidueParams};
+
+/// Precomputed powers of a fixed base `DynResidue`, for fast constant-time exponentiation of that base.
+///
+/// Building the table costs about as much as four exponentiations; afterwards every exponentiation costs one multiplication per four exponent bits and no squarings.
+/// The powers are kept in a buffer provided by the caller, which holds [`DynFixedBaseTable::LEN`] residues (32 KiB for a 256-bit modulus, 2 MiB for a 2048-bit one) and is best allocated on the heap.
+#[derive(Debug, Clone, Copy)]
+pub struct DynFixedBaseTable<'a, const LIMBS: usize> {
+    powers: &'a [UInt<LIMBS>],
+    residue_params: DynResidueParams<LIMBS>,
+}
+
+impl<'a, const LIMBS: usize> DynFixedBaseTable<'a, LIMBS> {
+    /// The number of residues the buffer of a table must hold.
+    pub const LEN: usize = fixed_base_table_len::<LIMBS>();
+
+    /// Precomputes the table of powers of `base` into `buffer`, overwriting its contents.
+    ///
+    /// Panics if `buffer` does not hold exactly [`DynFixedBaseTable::LEN`] residues.
+    pub fn new(base: &DynResidue<LIMBS>, buffer: &'a mut [UInt<LIMBS>]) -> Self {
+        let residue_params = base.residue_params;
+        compute_fixed_base_powers(
+            buffer,
+            base.montgomery_form,
+            residue_params.modulus,
+            residue_params.r,
+            residue_params.mod_neg_inv,
+        );
+
+        Self {
+            powers: buffer,
+            residue_params,
+        }
+    }
+
+    /// Computes `base^exponent` in constant time, where `base` is the residue this table was built from.
+    ///
+    /// The exponent may have fewer limbs than the residue, which makes this correspondingly faster; exponents with more limbs fail to compile.
+    pub fn pow<const RHS_LIMBS: usize>(&self, exponent: &UInt<RHS_LIMBS>) -> DynResidue<LIMBS> {
+        DynResidue {
+            montgomery_form: pow_fixed_base_montgomery_form(
+                self.powers,
+                exponent,
+                self.residue_params.modulus,
+                self.residue_params.r,
+                self.residue_params.mod_neg_inv,
+            ),
+            residue_params: self.residue_params,
+        }
+    }
+}
 
 impl<const LIMBS: usize> PowResidue<LIMBS> for DynResidue<LIMBS> {
-    fn pow_specific(self, exponent: &UInt<LIMBS>, exponent_bits: usize) -> Self {
+    fn pow_specific<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self {
         self.pow_specific(exponent, exponent_bits)
     }
 }
 
 impl<const LIMBS: usize> DynResidue<LIMBS> {
     /// Computes the (reduced) exponentiation of a residue, here `exponent_bits` represents the number of bits to take into account for the exponent. Note that this value is leaked in the time pattern.
-    pub const fn pow_specific(self, exponent: &UInt<LIMBS>, exponent_bits: usize) -> Self {
+    /// The exponent may have a different number of limbs than the residue.
+    pub const fn pow_specific<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self {
         Self {
             montgomery_form: pow_montgomery_form(
                 self.montgomery_form,
@@ -26,4 +93,189 @@ impl<const LIMBS: usize> DynResidue<LIMBS> {
             residue_params: self.residue_params,
         }
     }
+
+    /// Performs modular exponentiation using a sliding window, skipping the leading zeros of the exponent.
+    ///
+    /// This is variable-time with respect to the exponent: only use it with public exponents, e.g. to verify signatures.
+    pub const fn pow_vartime<const RHS_LIMBS: usize>(self, exponent: &UInt<RHS_LIMBS>) -> Self {
+        Self {
+            montgomery_form: pow_montgomery_form_vartime(
+                self.montgomery_form,
+                exponent,
+                self.residue_params.modulus,
+                self.residue_params.r,
+                self.residue_params.mod_neg_inv,
+            ),
+            residue_params: self.residue_params,
+        }
+    }
+
+    /// Computes the product of `base^exponent` over all the given pairs in constant time.
+    /// All bases must have the given `residue_params`.
+    ///
+    /// The bases share their squarings (Straus' method), which is considerably faster than exponentiating each base separately.
+    /// The product of no pairs is one.
+    pub fn multi_exponentiate<const RHS_LIMBS: usize>(
+        bases_and_exponents: &[(Self, UInt<RHS_LIMBS>)],
+        residue_params: &DynResidueParams<LIMBS>,
+    ) -> Self {
+        Self::multi_exponentiate_with(
+            bases_and_exponents,
+            residue_params,
+            multi_exponentiate_montgomery_form,
+        )
+    }
+
+    /// Computes the product of `base^exponent` over all the given pairs, like [`DynResidue::multi_exponentiate`].
+    ///
+    /// This is variable-time with respect to the exponents: only use it with public exponents.
+    pub fn multi_exponentiate_vartime<const RHS_LIMBS: usize>(
+        bases_and_exponents: &[(Self, UInt<RHS_LIMBS>)],
+        residue_params: &DynResidueParams<LIMBS>,
+    ) -> Self {
+        Self::multi_exponentiate_with(
+            bases_and_exponents,
+            residue_params,
+            multi_exponentiate_montgomery_form_vartime,
+        )
+    }
+
+    fn multi_exponentiate_with<const RHS_LIMBS: usize>(
+        bases_and_exponents: &[(Self, UInt<RHS_LIMBS>)],
+        residue_params: &DynResidueParams<LIMBS>,
+        multi_exponentiate: MultiExponentiation<LIMBS, RHS_LIMBS>,
+    ) -> Self {
+        Self {
+            montgomery_form: multi_exponentiate_chunked(
+                bases_and_exponents,
+                |(base, exponent)| {
+                    debug_assert_eq!(&base.residue_params, residue_params);
+                    (base.montgomery_form, *exponent)
+                },
+                multi_exponentiate,
+                residue_params.modulus,
+                residue_params.r,
+                residue_params.mod_neg_inv,
+            ),
+            residue_params: *residue_params,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        modular::{
+            runtime_mod::{DynFixedBaseTable, DynResidue, DynResidueParams},
+            PowResidue,
+        },
+        U128, U256, U512,
+    };
+
+    #[test]
+    fn test_powmod_narrow_exponent() {
+        let params = DynResidueParams::new(U512::from_be_hex(concat![
+            "B5BF992DC9E9C616612E7696A6CECC1B78E510617311D8A3C2CE6F447ED4D57B",
+            "1E2FEB89414C343C1027C4D1C386BBC4CD613E30D8F16ADF91B7584A2265B1F5"
+        ]));
+        let base = U512::from_be_hex(concat![
+            "33511F8DB8B6D8FE442E3D437204E52DB2221A58008A05A6C4647159C324C985",
+            "9B810E766EC9D28663CA828DD5F4B3B2E4B06CE60741C7A87CE42C8218072E8C"
+        ]);
+        let exponent = U128::from_be_hex("1A2B8F1FF1FD42A29755D4C13A902931");
+
+        let res = DynResidue::new(base, params).pow(&exponent);
+
+        let expected = U512::from_be_hex(concat![
+            "81EC2270B0C63DC45FB4C8C8E42710AD4D9E0D65CA1482151869D48B52E9B5C4",
+            "4F894B29C2A9EF9E217C4EB37886571CFF1E29308064E6D695B5DEBF2BA2DC14"
+        ]);
+        assert_eq!(res.retrieve(), expected);
+        assert_eq!(
+            res,
+            DynResidue::new(base, params).pow(&exponent.resize::<{ U512::LIMBS }>())
+        );
+        assert_eq!(res, DynResidue::new(base, params).pow_vartime(&exponent));
+    }
+
+    #[test]
+    fn test_multi_exponentiate() {
+        let params = DynResidueParams::new(U256::from_be_hex(
+            "9CC24C5DF431A864188AB905AC751B727C9447A8E99E6366E1AD78A21E8D882B",
+        ));
+
+        let base =
+            U256::from_be_hex("3435D18AA8313EBBE4D20002922225B53F75DC4453BB3EEC0378646F79B524A4");
+        let exponent =
+            U256::from_be_hex("77117F1273373C26C700D076B3F780074D03339F56DD0EFB60E7F58441FD3685");
+        let base2 = U256::from(105u64);
+        let exponent2 =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+
+        let expected =
+            U256::from_be_hex("66A511DAF022790521B16B3AA7DAE2A2749FA9EFA221FAA3DE3F5CE7DEC06C1D");
+
+        let pairs = [
+            (DynResidue::new(base, params), exponent),
+            (DynResidue::new(base2, params), exponent2),
+        ];
+        assert_eq!(
+            DynResidue::multi_exponentiate(&pairs, &params).retrieve(),
+            expected
+        );
+        assert_eq!(
+            DynResidue::multi_exponentiate_vartime(&pairs, &params).retrieve(),
+            expected
+        );
+
+        let empty: [(DynResidue<{ U256::LIMBS }>, U256); 0] = [];
+        assert_eq!(
+            DynResidue::multi_exponentiate(&empty, &params),
+            DynResidue::one(params)
+        );
+        assert_eq!(
+            DynResidue::multi_exponentiate_vartime(&empty, &params),
+            DynResidue::one(params)
+        );
+    }
+
+    #[test]
+    fn test_fixed_base_table() {
+        let params = DynResidueParams::new(U256::from_be_hex(
+            "9CC24C5DF431A864188AB905AC751B727C9447A8E99E6366E1AD78A21E8D882B",
+        ));
+
+        let base = U256::from(105u64);
+        let mut buffer = [U256::ZERO; DynFixedBaseTable::<{ U256::LIMBS }>::LEN];
+        let table = DynFixedBaseTable::new(&DynResidue::new(base, params), &mut buffer);
+
+        // 105^(p - 2) is the inverse of 105
+        let exponent =
+            U256::from_be_hex("9CC24C5DF431A864188AB905AC751B727C9447A8E99E6366E1AD78A21E8D8829");
+        let expected =
+            U256::from_be_hex("6432A3E5ED34D76C15B552A5E7449C00628B3C55B330F3F7D50B08E50A0C3AC0");
+        assert_eq!(table.pow(&exponent).retrieve(), expected);
+
+        // Narrower exponents only use the first windows of the table
+        let exponent = U128::from_u128(0xD50B08E50A0C3AC0_9CC24C5DF431A864);
+        assert_eq!(
+            table.pow(&exponent),
+            DynResidue::new(base, params).pow(&exponent)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_fixed_base_table_large() {
+        use crate::U2048;
+
+        let params = DynResidueParams::new(U2048::MAX);
+        let base = DynResidue::new(U2048::from(105u64), params);
+
+        let mut buffer = alloc::vec![U2048::ZERO; DynFixedBaseTable::<{ U2048::LIMBS }>::LEN];
+        let table = DynFixedBaseTable::new(&base, &mut buffer);
+
+        let exponent = U2048::MAX.wrapping_sub(&U2048::from(1234u64));
+        assert_eq!(table.pow(&exponent), base.pow(&exponent));
+    }
 }
@@ -0,0 +1,70 @@
+//! Random residue generation with a modulus set at runtime
+
+use rand_core::{CryptoRng, RngCore};
+
+use super::{DynResidue, DynResidueParams};
+use crate::{NonZero, RandomMod, UInt};
+
+impl<const LIMBS: usize> DynResidue<LIMBS> {
+    /// Generate a cryptographically secure, uniformly random residue with the given parameters.
+    ///
+    /// Since multiplication by `R` is a bijection, the Montgomery form is sampled directly.
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+    pub fn random(rng: impl CryptoRng + RngCore, residue_params: &DynResidueParams<LIMBS>) -> Self {
+        let modulus = NonZero::new(residue_params.modulus).unwrap();
+        Self {
+            montgomery_form: UInt::random_mod(rng, &modulus),
+            residue_params: *residue_params,
+        }
+    }
+
+    /// Generate a cryptographically secure, uniformly random non-zero residue with the given parameters.
+    ///
+    /// Panics if the modulus is 1, since there are no non-zero residues then.
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+    pub fn random_nonzero(
+        rng: impl CryptoRng + RngCore,
+        residue_params: &DynResidueParams<LIMBS>,
+    ) -> Self {
+        // The Montgomery form is zero only for the zero residue, so sample it from [1, modulus)
+        let bound = NonZero::new(residue_params.modulus.wrapping_sub(&UInt::ONE)).unwrap();
+        Self {
+            montgomery_form: UInt::random_mod(rng, &bound).wrapping_add(&UInt::ONE),
+            residue_params: *residue_params,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::SeedableRng;
+
+    use crate::{
+        modular::runtime_mod::{DynResidue, DynResidueParams},
+        U256,
+    };
+
+    #[test]
+    fn random() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        let modulus =
+            U256::from_be_hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+        let params = DynResidueParams::new(modulus);
+
+        let x = DynResidue::random(&mut rng, &params);
+        assert!(x.as_montgomery() < &modulus);
+        assert_eq!(x.params(), &params);
+
+        let y = DynResidue::random_nonzero(&mut rng, &params);
+        assert!(y.as_montgomery() < &modulus);
+        assert_ne!(y.retrieve(), U256::ZERO);
+        assert_ne!(x, y);
+
+        // Modulo 3, zero is a third of all residues
+        let params = DynResidueParams::new(U256::from(3u8));
+        for _ in 0..8 {
+            let z = DynResidue::random_nonzero(&mut rng, &params);
+            assert_ne!(z.retrieve(), U256::ZERO);
+        }
+    }
+}
@@ -0,0 +1,466 @@
+use core::ops::Neg;
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::{
+    modular::{
+        add::add_montgomery_form,
+        inv::inv_montgomery_form,
+        mul::{mul_montgomery_form, square_montgomery_form},
+        neg::neg_montgomery_form,
+        pow::{pow_montgomery_form, pow_montgomery_form_vartime},
+        reduction::montgomery_reduction,
+        sub::sub_montgomery_form,
+        AddResidue, GenericResidue, InvResidue, MulResidue, NegResidue, PowResidue, SubResidue,
+    },
+    JacobiSymbol, UInt, Word,
+};
+
+use super::{DynResidue, DynResidueParams};
+
+/// A residue represented using `LIMBS` limbs, which borrows the parameters of its odd modulus set at runtime.
+///
+/// This has the same arithmetic as [`DynResidue`], but only stores the Montgomery form of the value next to a reference to the parameters, so it is much smaller and the parameters are never copied.
+#[derive(Debug, Clone, Copy)]
+pub struct DynResidueRef<'a, const LIMBS: usize> {
+    montgomery_form: UInt<LIMBS>,
+    residue_params: &'a DynResidueParams<LIMBS>,
+}
+
+impl<'a, const LIMBS: usize> DynResidueRef<'a, LIMBS> {
+    /// Instantiates a new `DynResidueRef` that represents this `integer` mod the modulus of `residue_params`.
+    pub const fn new(integer: UInt<LIMBS>, residue_params: &'a DynResidueParams<LIMBS>) -> Self {
+        let product = integer.mul_wide(&residue_params.r2);
+        Self {
+            montgomery_form: montgomery_reduction(
+                product,
+                residue_params.modulus,
+                residue_params.mod_neg_inv,
+            ),
+            residue_params,
+        }
+    }
+
+    /// Retrieves the integer currently encoded in this residue, guaranteed to be reduced.
+    pub const fn retrieve(&self) -> UInt<LIMBS> {
+        montgomery_reduction(
+            (self.montgomery_form, UInt::ZERO),
+            self.residue_params.modulus,
+            self.residue_params.mod_neg_inv,
+        )
+    }
+
+    /// Instantiates a new residue from its Montgomery form `x * R mod modulus`, where `R = 2^(LIMBS * Word::BITS)`.
+    ///
+    /// The Montgomery form must be reduced, i.e. less than the modulus; this is not checked.
+    pub const fn from_montgomery(
+        montgomery_form: UInt<LIMBS>,
+        residue_params: &'a DynResidueParams<LIMBS>,
+    ) -> Self {
+        Self {
+            montgomery_form,
+            residue_params,
+        }
+    }
+
+    /// Returns the Montgomery form `x * R mod modulus` of this residue, where `R = 2^(LIMBS * Word::BITS)`.
+    pub const fn as_montgomery(&self) -> &UInt<LIMBS> {
+        &self.montgomery_form
+    }
+
+    /// Returns the parameters of this residue.
+    pub const fn params(&self) -> &'a DynResidueParams<LIMBS> {
+        self.residue_params
+    }
+
+    /// Computes the Legendre symbol of this residue in constant time, assuming the modulus is an odd prime.
+    pub const fn legendre(&self) -> JacobiSymbol {
+        self.retrieve().jacobi(&self.residue_params.modulus)
+    }
+
+    /// Adds `rhs`.
+    pub const fn add(&self, rhs: &Self) -> Self {
+        Self {
+            montgomery_form: add_montgomery_form(
+                &self.montgomery_form,
+                &rhs.montgomery_form,
+                &self.residue_params.modulus,
+            ),
+            residue_params: self.residue_params,
+        }
+    }
+
+    /// Subtracts `rhs`.
+    pub const fn sub(&self, rhs: &Self) -> Self {
+        Self {
+            montgomery_form: sub_montgomery_form(
+                &self.montgomery_form,
+                &rhs.montgomery_form,
+                &self.residue_params.modulus,
+            ),
+            residue_params: self.residue_params,
+        }
+    }
+
+    /// Negates the residue.
+    pub const fn neg(&self) -> Self {
+        Self {
+            montgomery_form: neg_montgomery_form(
+                &self.montgomery_form,
+                &self.residue_params.modulus,
+            ),
+            residue_params: self.residue_params,
+        }
+    }
+
+    /// Multiplies by `rhs`.
+    pub const fn mul(&self, rhs: &Self) -> Self {
+        Self {
+            montgomery_form: mul_montgomery_form(
+                &self.montgomery_form,
+                &rhs.montgomery_form,
+                self.residue_params.modulus,
+                self.residue_params.mod_neg_inv,
+            ),
+            residue_params: self.residue_params,
+        }
+    }
+
+    /// Computes the (reduced) square of a residue.
+    pub const fn square(&self) -> Self {
+        Self {
+            montgomery_form: square_montgomery_form(
+                &self.montgomery_form,
+                self.residue_params.modulus,
+                self.residue_params.mod_neg_inv,
+            ),
+            residue_params: self.residue_params,
+        }
+    }
+
+    /// Computes the (reduced) exponentiation of a residue, here `exponent_bits` represents the number of bits to take into account for the exponent. Note that this value is leaked in the time pattern.
+    pub const fn pow_specific<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self {
+        Self {
+            montgomery_form: pow_montgomery_form(
+                self.montgomery_form,
+                exponent,
+                exponent_bits,
+                self.residue_params.modulus,
+                self.residue_params.r,
+                self.residue_params.mod_neg_inv,
+            ),
+            residue_params: self.residue_params,
+        }
+    }
+
+    /// Performs modular exponentiation using a sliding window, skipping the leading zeros of the exponent.
+    ///
+    /// This is variable-time with respect to the exponent: only use it with public exponents.
+    pub const fn pow_vartime<const RHS_LIMBS: usize>(self, exponent: &UInt<RHS_LIMBS>) -> Self {
+        Self {
+            montgomery_form: pow_montgomery_form_vartime(
+                self.montgomery_form,
+                exponent,
+                self.residue_params.modulus,
+                self.residue_params.r,
+                self.residue_params.mod_neg_inv,
+            ),
+            residue_params: self.residue_params,
+        }
+    }
+}
+
+impl<const LIMBS: usize> AddResidue for DynResidueRef<'_, LIMBS> {
+    fn add(&self, rhs: &Self) -> Self {
+        debug_assert_eq!(self.residue_params, rhs.residue_params);
+        DynResidueRef::add(self, rhs)
+    }
+}
+
+impl<const LIMBS: usize> SubResidue for DynResidueRef<'_, LIMBS> {
+    fn sub(&self, rhs: &Self) -> Self {
+        debug_assert_eq!(self.residue_params, rhs.residue_params);
+        DynResidueRef::sub(self, rhs)
+    }
+}
+
+impl<const LIMBS: usize> NegResidue for DynResidueRef<'_, LIMBS> {
+    fn neg(&self) -> Self {
+        DynResidueRef::neg(self)
+    }
+}
+
+impl<const LIMBS: usize> MulResidue for DynResidueRef<'_, LIMBS> {
+    fn mul(&self, rhs: &Self) -> Self {
+        debug_assert_eq!(self.residue_params, rhs.residue_params);
+        DynResidueRef::mul(self, rhs)
+    }
+
+    fn square(&self) -> Self {
+        DynResidueRef::square(self)
+    }
+}
+
+impl<const LIMBS: usize> PowResidue<LIMBS> for DynResidueRef<'_, LIMBS> {
+    fn pow_specific<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self {
+        DynResidueRef::pow_specific(self, exponent, exponent_bits)
+    }
+}
+
+impl<const LIMBS: usize> InvResidue for DynResidueRef<'_, LIMBS> {
+    fn inv(self) -> CtOption<Self> {
+        let (montgomery_form, error) = inv_montgomery_form(
+            self.montgomery_form,
+            self.residue_params.modulus,
+            &self.residue_params.r3,
+            self.residue_params.mod_neg_inv,
+        );
+
+        let value = Self {
+            montgomery_form,
+            residue_params: self.residue_params,
+        };
+
+        CtOption::new(value, Choice::from((error == Word::MAX) as u8))
+    }
+}
+
+impl<const LIMBS: usize> GenericResidue<LIMBS> for DynResidueRef<'_, LIMBS> {
+    fn retrieve(&self) -> UInt<LIMBS> {
+        DynResidueRef::retrieve(self)
+    }
+}
+
+impl<'a, const LIMBS: usize> From<&'a DynResidue<LIMBS>> for DynResidueRef<'a, LIMBS> {
+    fn from(residue: &'a DynResidue<LIMBS>) -> Self {
+        Self {
+            montgomery_form: residue.montgomery_form,
+            residue_params: &residue.residue_params,
+        }
+    }
+}
+
+impl<const LIMBS: usize> From<DynResidueRef<'_, LIMBS>> for DynResidue<LIMBS> {
+    fn from(residue: DynResidueRef<'_, LIMBS>) -> Self {
+        Self {
+            montgomery_form: residue.montgomery_form,
+            residue_params: *residue.residue_params,
+        }
+    }
+}
+
+impl_residue_binary_op!(
+    ['a, const LIMBS: usize] DynResidueRef<'a, LIMBS>,
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    AddResidue::add
+);
+
+impl_residue_binary_op!(
+    ['a, const LIMBS: usize] DynResidueRef<'a, LIMBS>,
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    SubResidue::sub
+);
+
+impl_residue_binary_op!(
+    ['a, const LIMBS: usize] DynResidueRef<'a, LIMBS>,
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    MulResidue::mul
+);
+
+/// Computes `lhs / rhs`, which is none if `rhs` is not invertible.
+fn div<'a, const LIMBS: usize>(
+    lhs: &DynResidueRef<'a, LIMBS>,
+    rhs: &DynResidueRef<'a, LIMBS>,
+) -> CtOption<DynResidueRef<'a, LIMBS>> {
+    let inverse = InvResidue::inv(*rhs);
+    let zero = DynResidueRef::from_montgomery(UInt::ZERO, rhs.residue_params);
+    let quotient = MulResidue::mul(lhs, &inverse.unwrap_or(zero));
+    CtOption::new(quotient, inverse.is_some())
+}
+
+impl_residue_binary_op!(
+    ['a, const LIMBS: usize] DynResidueRef<'a, LIMBS>,
+    CtOption<DynResidueRef<'a, LIMBS>>,
+    Div,
+    div,
+    div
+);
+
+impl<const LIMBS: usize> Neg for DynResidueRef<'_, LIMBS> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        DynResidueRef::neg(&self)
+    }
+}
+
+impl<'a, const LIMBS: usize> Neg for &DynResidueRef<'a, LIMBS> {
+    type Output = DynResidueRef<'a, LIMBS>;
+
+    fn neg(self) -> DynResidueRef<'a, LIMBS> {
+        DynResidueRef::neg(self)
+    }
+}
+
+impl<const LIMBS: usize> ConstantTimeEq for DynResidueRef<'_, LIMBS> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.montgomery_form.ct_eq(&other.montgomery_form)
+            & self.residue_params.ct_eq(other.residue_params)
+    }
+}
+
+impl<const LIMBS: usize> PartialEq for DynResidueRef<'_, LIMBS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const LIMBS: usize> Eq for DynResidueRef<'_, LIMBS> {}
+
+/// Both residues must have the same parameters, as for the arithmetic operations: a reference cannot be selected in constant time, so the parameters of `a` are kept.
+impl<const LIMBS: usize> ConditionallySelectable for DynResidueRef<'_, LIMBS> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        debug_assert_eq!(a.residue_params, b.residue_params);
+        Self {
+            montgomery_form: UInt::conditional_select(
+                &a.montgomery_form,
+                &b.montgomery_form,
+                choice,
+            ),
+            residue_params: a.residue_params,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::size_of;
+
+    use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+    use super::DynResidueRef;
+    use crate::{
+        modular::{
+            runtime_mod::{DynResidue, DynResidueParams},
+            InvResidue, MulResidue, PowResidue,
+        },
+        U256,
+    };
+
+    #[test]
+    fn matches_dyn_residue() {
+        let params = DynResidueParams::new(U256::from_be_hex(
+            "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+        ));
+        let x =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+        let y =
+            U256::from_be_hex("d5777c45019673125ad240f83094d4252d829516fac8601ed01979ec1ec1a251");
+
+        let (x_dyn, y_dyn) = (DynResidue::new(x, params), DynResidue::new(y, params));
+        let (x_ref, y_ref) = (
+            DynResidueRef::new(x, &params),
+            DynResidueRef::new(y, &params),
+        );
+        assert_eq!(DynResidueRef::from(&x_dyn), x_ref);
+
+        assert_eq!((x_ref + y_ref).retrieve(), (x_dyn + y_dyn).retrieve());
+        assert_eq!((x_ref - y_ref).retrieve(), (x_dyn - y_dyn).retrieve());
+        assert_eq!(
+            MulResidue::mul(&x_ref, &y_ref).retrieve(),
+            MulResidue::mul(&x_dyn, &y_dyn).retrieve()
+        );
+        assert_eq!((-x_ref + x_ref).retrieve(), U256::ZERO);
+        assert_eq!(DynResidue::from(x_ref.pow(&y)), x_dyn.pow(&y));
+        assert_eq!(DynResidue::from(x_ref.inv().unwrap()), x_dyn.inv().unwrap());
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn operators() {
+        let params = DynResidueParams::new(U256::from_be_hex(
+            "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+        ));
+        let x =
+            U256::from_be_hex("44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56");
+        let y =
+            U256::from_be_hex("d5777c45019673125ad240f83094d4252d829516fac8601ed01979ec1ec1a251");
+
+        let (a, b) = (
+            DynResidueRef::new(x, &params),
+            DynResidueRef::new(y, &params),
+        );
+        let product = a * b;
+        assert_eq!(
+            DynResidue::from(product),
+            DynResidue::new(x, params) * DynResidue::new(y, params)
+        );
+        assert_eq!(&a * &b, product);
+        assert_eq!(a * &b, product);
+        assert_eq!(&a * b, product);
+        assert_eq!(&a + &b - &b, a);
+        assert_eq!(-&a, -a);
+        assert_eq!((product / b).unwrap(), a);
+        assert_eq!((&product / &b).unwrap(), a);
+        let zero = DynResidueRef::new(U256::ZERO, &params);
+        assert!(bool::from((a / zero).is_none()));
+
+        let mut c = a;
+        c *= &b;
+        c -= a;
+        c += &a;
+        assert_eq!(c, product);
+    }
+
+    #[test]
+    fn constant_time_traits() {
+        let params = DynResidueParams::new(U256::from_be_hex(
+            "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+        ));
+        let other_params = DynResidueParams::new(U256::from_be_hex(
+            "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+        ));
+        let x = DynResidueRef::new(U256::from(3u64), &params);
+        let y = DynResidueRef::new(U256::from(5u64), &params);
+
+        assert!(bool::from(x.ct_eq(&x)));
+        assert!(!bool::from(x.ct_eq(&y)));
+        assert!(!bool::from(
+            x.ct_eq(&DynResidueRef::new(U256::from(3u64), &other_params))
+        ));
+        // Equal parameters behind different references compare equal
+        let params_copy = params;
+        assert_eq!(x, DynResidueRef::new(U256::from(3u64), &params_copy));
+
+        assert_eq!(
+            DynResidueRef::conditional_select(&x, &y, Choice::from(0)),
+            x
+        );
+        assert_eq!(
+            DynResidueRef::conditional_select(&x, &y, Choice::from(1)),
+            y
+        );
+    }
+
+    #[test]
+    fn size() {
+        assert!(size_of::<DynResidueRef<'_, { U256::LIMBS }>>() < 2 * size_of::<U256>());
+    }
+}
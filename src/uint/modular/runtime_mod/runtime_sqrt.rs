@@ -0,0 +1,209 @@
+use subtle::{Choice, CtOption};
+
+use crate::{
+    modular::{
+        pow::pow_montgomery_form_vartime,
+        reduction::montgomery_reduction,
+        sqrt::{find_non_residue, sqrt_montgomery_form, two_adicity},
+        SqrtResidue,
+    },
+    UInt, Word,
+};
+
+use super::{DynResidue, DynResidueParams, DynResidueRef};
+
+/// The constants needed to compute square roots modulo an odd prime set at runtime.
+///
+/// For a modulus `p = 1 mod 8`, Tonelli-Shanks needs a primitive root of unity, which is derived from a quadratic non-residue found by a search whose duration depends on the modulus.
+/// To keep that cost out of [`DynResidueParams`], it is paid once when creating these constants, which can then be passed to [`DynResidue::sqrt_with`] for every square root.
+#[derive(Debug, Clone, Copy)]
+pub struct DynSqrtParams<const LIMBS: usize> {
+    // The modulus these constants belong to
+    modulus: UInt<LIMBS>,
+    // The 2-adicity `s` of `modulus - 1`
+    two_adicity: usize,
+    // The odd part of `modulus - 1`
+    odd_part: UInt<LIMBS>,
+    // A primitive `2^s`-th root of unity in Montgomery form
+    root_of_unity: UInt<LIMBS>,
+}
+
+impl<const LIMBS: usize> DynSqrtParams<LIMBS> {
+    /// Computes the square root constants for the modulus of `residue_params`, which must be an odd prime.
+    ///
+    /// This is variable-time with respect to the modulus if `modulus = 1 mod 8`, the only case in which the constants are needed; for the other moduli it does no work.
+    /// If no quadratic non-residue is found, which only happens if the modulus is not prime, square roots computed with these constants may be reported as none.
+    pub const fn new(residue_params: &DynResidueParams<LIMBS>) -> Self {
+        let modulus = residue_params.modulus;
+        let none = Self {
+            modulus,
+            two_adicity: 0,
+            odd_part: UInt::ZERO,
+            root_of_unity: UInt::ZERO,
+        };
+
+        if modulus.limbs[0].0 & 7 != 1 {
+            return none;
+        }
+
+        let non_residue = match find_non_residue(&modulus) {
+            Some(non_residue) => non_residue,
+            None => return none,
+        };
+
+        let two_adicity = two_adicity(&modulus);
+        let odd_part = modulus.shr_vartime(two_adicity);
+        let non_residue = montgomery_reduction(
+            non_residue.mul_wide(&residue_params.r2),
+            modulus,
+            residue_params.mod_neg_inv,
+        );
+        let root_of_unity = pow_montgomery_form_vartime(
+            non_residue,
+            &odd_part,
+            modulus,
+            residue_params.r,
+            residue_params.mod_neg_inv,
+        );
+
+        Self {
+            modulus,
+            two_adicity,
+            odd_part,
+            root_of_unity,
+        }
+    }
+
+    /// Computes a square root of `x`, given in Montgomery form for `residue_params`.
+    pub(super) const fn sqrt_montgomery_form(
+        &self,
+        x: UInt<LIMBS>,
+        residue_params: &DynResidueParams<LIMBS>,
+    ) -> (UInt<LIMBS>, Word) {
+        sqrt_montgomery_form(
+            x,
+            residue_params.modulus,
+            residue_params.r,
+            residue_params.mod_neg_inv,
+            self.two_adicity,
+            &self.odd_part,
+            &self.root_of_unity,
+        )
+    }
+}
+
+impl<const LIMBS: usize> DynResidue<LIMBS> {
+    /// Computes a square root of this residue using the precomputed `sqrt_params`, assuming the modulus is an odd prime. Returns none if the residue is not a quadratic residue.
+    ///
+    /// `sqrt_params` must have been created for the parameters of this residue.
+    pub fn sqrt_with(&self, sqrt_params: &DynSqrtParams<LIMBS>) -> CtOption<Self> {
+        debug_assert_eq!(sqrt_params.modulus, self.residue_params.modulus);
+        let (montgomery_form, is_some) =
+            sqrt_params.sqrt_montgomery_form(self.montgomery_form, &self.residue_params);
+
+        let value = Self {
+            montgomery_form,
+            residue_params: self.residue_params,
+        };
+
+        CtOption::new(value, Choice::from((is_some == Word::MAX) as u8))
+    }
+}
+
+impl<'a, const LIMBS: usize> DynResidueRef<'a, LIMBS> {
+    /// Computes a square root of this residue using the precomputed `sqrt_params`, assuming the modulus is an odd prime. Returns none if the residue is not a quadratic residue.
+    ///
+    /// `sqrt_params` must have been created for the parameters of this residue.
+    pub fn sqrt_with(&self, sqrt_params: &DynSqrtParams<LIMBS>) -> CtOption<Self> {
+        debug_assert_eq!(sqrt_params.modulus, *self.params().modulus());
+        let (montgomery_form, is_some) =
+            sqrt_params.sqrt_montgomery_form(*self.as_montgomery(), self.params());
+        let value = Self::from_montgomery(montgomery_form, self.params());
+        CtOption::new(value, Choice::from((is_some == Word::MAX) as u8))
+    }
+}
+
+/// For a modulus `p = 1 mod 8`, this computes the [`DynSqrtParams`] on every call; use [`DynResidue::sqrt_with`] to compute them only once.
+impl<const LIMBS: usize> SqrtResidue for DynResidue<LIMBS> {
+    fn sqrt(&self) -> CtOption<Self> {
+        self.sqrt_with(&DynSqrtParams::new(&self.residue_params))
+    }
+}
+
+/// For a modulus `p = 1 mod 8`, this computes the [`DynSqrtParams`] on every call; use [`DynResidueRef::sqrt_with`] to compute them only once.
+impl<const LIMBS: usize> SqrtResidue for DynResidueRef<'_, LIMBS> {
+    fn sqrt(&self) -> CtOption<Self> {
+        self.sqrt_with(&DynSqrtParams::new(self.params()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        modular::{
+            runtime_mod::{DynResidue, DynResidueParams, DynResidueRef, DynSqrtParams},
+            MulResidue, SqrtResidue,
+        },
+        U256,
+    };
+
+    macro_rules! test_sqrt {
+        ($test_name:ident, $modulus:expr) => {
+            #[test]
+            fn $test_name() {
+                let params = DynResidueParams::new(U256::from_be_hex($modulus));
+
+                let x = U256::from_be_hex(
+                    "44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56",
+                );
+                let x_mod = DynResidue::new(x, params);
+                let square = x_mod.square();
+
+                let root = square.sqrt().unwrap();
+                assert_eq!(root.square(), square);
+                assert!(root == x_mod || root == -x_mod);
+
+                let zero = DynResidue::zero(params);
+                assert_eq!(zero.sqrt().unwrap(), zero);
+
+                let sqrt_params = DynSqrtParams::new(&params);
+                assert_eq!(square.sqrt_with(&sqrt_params).unwrap(), root);
+                let square_ref = DynResidueRef::from(&square);
+                assert_eq!(
+                    DynResidue::from(square_ref.sqrt_with(&sqrt_params).unwrap()),
+                    root
+                );
+                assert_eq!(DynResidue::from(square_ref.sqrt().unwrap()), root);
+            }
+        };
+    }
+
+    // p = 3 mod 4
+    test_sqrt!(
+        sqrt_3_mod_4,
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+    );
+
+    // p = 5 mod 8
+    test_sqrt!(
+        sqrt_5_mod_8,
+        "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"
+    );
+
+    // p = 1 mod 16
+    test_sqrt!(
+        sqrt_tonelli_shanks,
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+    );
+
+    #[test]
+    fn sqrt_non_residue() {
+        // 7 is a quadratic non-residue modulo the BLS12-381 scalar field
+        let params = DynResidueParams::new(U256::from_be_hex(
+            "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+        ));
+        let seven = DynResidue::new(U256::from(7u64), params);
+        assert!(bool::from(seven.sqrt().is_none()));
+        assert_eq!((seven * seven).sqrt().unwrap().square(), seven * seven);
+    }
+}
@@ -0,0 +1,574 @@
+use core::{
+    iter::{Product, Sum},
+    marker::PhantomData,
+    ops::Neg,
+};
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::{JacobiSymbol, Limb, UInt, Word};
+
+use super::{
+    pow::{ct_lookup, window_index, PowerTable, WINDOW},
+    runtime_mod::{DynResidue, DynResidueParams, DynSqrtParams},
+    AddResidue, GenericResidue, InvResidue, MulResidue, NegResidue, PowResidue, SqrtResidue,
+    SubResidue,
+};
+
+/// Ready-made parameters and dedicated reductions for the NIST primes
+mod nist;
+
+pub use self::nist::{NistP192, NistP224, NistP256, NistP384, NistP521};
+
+/// The parameters of a prime modulus with a dedicated (Solinas) reduction, such as the NIST primes.
+pub trait SolinasParams<const LIMBS: usize>: Copy {
+    /// The constant modulus
+    const MODULUS: UInt<LIMBS>;
+    /// The reduction modulo `MODULUS`
+    const REDUCTION: SolinasReduction;
+}
+
+/// A dedicated reduction modulo a Solinas prime, which must match [`SolinasParams::MODULUS`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum SolinasReduction {
+    /// The fast reduction modulo the NIST P-192 prime `2^192 - 2^64 - 1` from FIPS 186-4, Appendix D.2.1.
+    NistP192,
+    /// The fast reduction modulo the NIST P-224 prime `2^224 - 2^96 + 1` from FIPS 186-4, Appendix D.2.2.
+    NistP224,
+    /// The fast reduction modulo the NIST P-256 prime `2^256 - 2^224 + 2^192 + 2^96 - 1` from FIPS 186-4, Appendix D.2.3.
+    NistP256,
+    /// The fast reduction modulo the NIST P-384 prime `2^384 - 2^128 - 2^96 + 2^32 - 1` from FIPS 186-4, Appendix D.2.4.
+    NistP384,
+    /// The modulus is the Mersenne prime `2^k - 1` with the given `k`, so that the bits from `k` upwards are simply added to the lower ones.
+    Mersenne(usize),
+}
+
+/// Reduces `x = lower_upper.0 + lower_upper.1 * 2^(LIMBS * Word::BITS)` modulo `MOD::MODULUS` in constant time. `x` must be less than `MOD::MODULUS^2`.
+///
+/// `MOD::REDUCTION` is a constant, so only the matching reduction is left after monomorphization.
+pub(crate) const fn reduce_wide<MOD: SolinasParams<LIMBS>, const LIMBS: usize>(
+    lower_upper: (UInt<LIMBS>, UInt<LIMBS>),
+) -> UInt<LIMBS> {
+    let reduced = match MOD::REDUCTION {
+        SolinasReduction::NistP192 => nist::reduce_p192(&lower_upper),
+        SolinasReduction::NistP224 => nist::reduce_p224(&lower_upper),
+        SolinasReduction::NistP256 => nist::reduce_p256(&lower_upper),
+        SolinasReduction::NistP384 => nist::reduce_p384(&lower_upper),
+        SolinasReduction::Mersenne(k) => {
+            // x = lo + 2^k hi = lo + hi mod p, and the sum is less than 2p
+            let lo = lower_upper
+                .0
+                .bitand(&UInt::ONE.shl_vartime(k).wrapping_sub(&UInt::ONE));
+            let hi = UInt::shr_vartime_wide(lower_upper, k).0;
+            lo.wrapping_add(&hi)
+        }
+    };
+
+    let (subtracted, borrow) = reduced.sbb(&MOD::MODULUS, Limb::ZERO);
+    UInt::ct_select(subtracted, reduced, borrow.0)
+}
+
+/// A residue mod `MOD`, represented using `LIMBS` limbs, for a modulus with a dedicated reduction.
+///
+/// The residue is stored as a plain integer in `[0, MOD::MODULUS)`, and products are reduced with [`SolinasParams::REDUCTION`] instead of Montgomery reduction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolinasResidue<MOD, const LIMBS: usize>
+where
+    MOD: SolinasParams<LIMBS>,
+{
+    value: UInt<LIMBS>,
+    phantom: PhantomData<MOD>,
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> SolinasResidue<MOD, LIMBS> {
+    /// The representation of 0 mod `MOD`.
+    pub const ZERO: Self = Self {
+        value: UInt::ZERO,
+        phantom: PhantomData,
+    };
+
+    /// The representation of 1 mod `MOD`.
+    pub const ONE: Self = Self {
+        value: UInt::ONE,
+        phantom: PhantomData,
+    };
+
+    /// The Montgomery parameters of the modulus, used for square roots.
+    const MONTGOMERY_PARAMS: DynResidueParams<LIMBS> = DynResidueParams::new(MOD::MODULUS);
+
+    /// The square root constants of the modulus.
+    const SQRT_PARAMS: DynSqrtParams<LIMBS> = DynSqrtParams::new(&Self::MONTGOMERY_PARAMS);
+
+    /// Instantiates a new `SolinasResidue` that represents this `integer` mod `MOD`.
+    pub const fn new(integer: UInt<LIMBS>) -> Self {
+        Self {
+            value: reduce_wide::<MOD, LIMBS>((integer, UInt::ZERO)),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Retrieves the integer currently encoded in this residue, guaranteed to be reduced.
+    pub const fn retrieve(&self) -> UInt<LIMBS> {
+        self.value
+    }
+
+    /// Computes the Legendre symbol of this residue in constant time, assuming `MOD` is an odd prime.
+    pub const fn legendre(&self) -> JacobiSymbol {
+        self.value.jacobi(&MOD::MODULUS)
+    }
+
+    /// Adds `rhs`.
+    pub const fn add(&self, rhs: &Self) -> Self {
+        Self {
+            value: self.value.add_mod(&rhs.value, &MOD::MODULUS),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Subtracts `rhs`.
+    pub const fn sub(&self, rhs: &Self) -> Self {
+        Self {
+            value: self.value.sub_mod(&rhs.value, &MOD::MODULUS),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Negates the residue.
+    pub const fn neg(&self) -> Self {
+        Self {
+            value: self.value.neg_mod(&MOD::MODULUS),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Multiplies by `rhs`.
+    pub const fn mul(&self, rhs: &Self) -> Self {
+        Self {
+            value: reduce_wide::<MOD, LIMBS>(self.value.mul_wide(&rhs.value)),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Computes the (reduced) square of a residue.
+    pub const fn square(&self) -> Self {
+        Self {
+            value: reduce_wide::<MOD, LIMBS>(self.value.square_wide()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Performs constant-time modular exponentiation.
+    /// The exponent may have a different number of limbs than the residue.
+    pub const fn pow<const RHS_LIMBS: usize>(&self, exponent: &UInt<RHS_LIMBS>) -> Self {
+        self.pow_specific(exponent, RHS_LIMBS * Limb::BIT_SIZE)
+    }
+
+    /// Performs modular exponentiation using a fixed window of `WINDOW` bits, taking `exponent_bits` bits of the exponent into account. Note that this value is leaked in the time pattern.
+    ///
+    /// As for residues in Montgomery form, every window costs `WINDOW` squarings and one multiplication, and the power is selected by scanning the whole table, so the exponent itself is not leaked.
+    pub const fn pow_specific<const RHS_LIMBS: usize>(
+        &self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self {
+        if exponent_bits == 0 {
+            return Self::ONE;
+        }
+
+        // powers[i] contains self^i
+        let mut powers: PowerTable<LIMBS> = [UInt::ONE; 1 << WINDOW];
+        powers[1] = self.value;
+        let mut i = 2;
+        while i < powers.len() {
+            powers[i] = reduce_wide::<MOD, LIMBS>(powers[i - 1].mul_wide(&self.value));
+            i += 1;
+        }
+
+        let mut z = Self::ONE;
+        let mut window_num = (exponent_bits + WINDOW - 1) / WINDOW;
+        let top_window = window_num - 1;
+
+        while window_num > 0 {
+            window_num -= 1;
+
+            let bit = window_num * WINDOW;
+            let mut idx = window_index(exponent, bit);
+
+            if window_num == top_window {
+                // Only take the requested bits into account, and skip the squarings of 1
+                if exponent_bits - bit < WINDOW {
+                    idx &= (1 << (exponent_bits - bit)) - 1;
+                }
+            } else {
+                let mut j = 0;
+                while j < WINDOW {
+                    z = Self::square(&z);
+                    j += 1;
+                }
+            }
+
+            let power = ct_lookup(&powers, idx);
+            z.value = reduce_wide::<MOD, LIMBS>(z.value.mul_wide(&power));
+        }
+
+        z
+    }
+
+    /// Performs modular exponentiation by square-and-multiply, skipping the leading zeros of the exponent and the multiplications for its zero bits.
+    ///
+    /// This is variable-time with respect to the exponent: only use it with public exponents.
+    pub const fn pow_vartime<const RHS_LIMBS: usize>(&self, exponent: &UInt<RHS_LIMBS>) -> Self {
+        let mut z = Self::ONE;
+        let mut i = exponent.bits_vartime();
+        while i > 0 {
+            i -= 1;
+            z = Self::square(&z);
+            if exponent.bit_vartime(i) == 1 {
+                z = Self::mul(&z, self);
+            }
+        }
+        z
+    }
+
+    /// Computes the residue `self^-1` representing the multiplicative inverse of `self`. I.e. `self * self^-1 = 1`. Panics if `self` was not invertible.
+    pub const fn inv(self) -> Self {
+        let (value, error) = self.value.inv_odd_mod(MOD::MODULUS);
+        assert!(error == Word::MAX);
+        Self {
+            value,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> AddResidue for SolinasResidue<MOD, LIMBS> {
+    fn add(&self, rhs: &Self) -> Self {
+        SolinasResidue::add(self, rhs)
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> SubResidue for SolinasResidue<MOD, LIMBS> {
+    fn sub(&self, rhs: &Self) -> Self {
+        SolinasResidue::sub(self, rhs)
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> NegResidue for SolinasResidue<MOD, LIMBS> {
+    fn neg(&self) -> Self {
+        SolinasResidue::neg(self)
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> MulResidue for SolinasResidue<MOD, LIMBS> {
+    fn mul(&self, rhs: &Self) -> Self {
+        SolinasResidue::mul(self, rhs)
+    }
+
+    fn square(&self) -> Self {
+        SolinasResidue::square(self)
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> PowResidue<LIMBS>
+    for SolinasResidue<MOD, LIMBS>
+{
+    fn pow_specific<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self {
+        SolinasResidue::pow_specific(&self, exponent, exponent_bits)
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> InvResidue for SolinasResidue<MOD, LIMBS> {
+    fn inv(self) -> CtOption<Self> {
+        let (value, error) = self.value.inv_odd_mod(MOD::MODULUS);
+        let inverse = Self {
+            value,
+            phantom: PhantomData,
+        };
+        CtOption::new(inverse, Choice::from((error == Word::MAX) as u8))
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> SqrtResidue for SolinasResidue<MOD, LIMBS> {
+    fn sqrt(&self) -> CtOption<Self> {
+        // The square root is computed in Montgomery form, using the generic algorithms
+        let params = Self::MONTGOMERY_PARAMS;
+        let root = DynResidue::new(self.value, params).sqrt_with(&Self::SQRT_PARAMS);
+        let value = Self {
+            value: root.unwrap_or(DynResidue::zero(params)).retrieve(),
+            phantom: PhantomData,
+        };
+        CtOption::new(value, root.is_some())
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> GenericResidue<LIMBS>
+    for SolinasResidue<MOD, LIMBS>
+{
+    fn retrieve(&self) -> UInt<LIMBS> {
+        SolinasResidue::retrieve(self)
+    }
+}
+
+impl_residue_binary_op!(
+    [MOD: SolinasParams<LIMBS>, const LIMBS: usize] SolinasResidue<MOD, LIMBS>,
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    SolinasResidue::add
+);
+
+impl_residue_binary_op!(
+    [MOD: SolinasParams<LIMBS>, const LIMBS: usize] SolinasResidue<MOD, LIMBS>,
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    SolinasResidue::sub
+);
+
+impl_residue_binary_op!(
+    [MOD: SolinasParams<LIMBS>, const LIMBS: usize] SolinasResidue<MOD, LIMBS>,
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    SolinasResidue::mul
+);
+
+/// Computes `lhs / rhs`, which is none if `rhs` is not invertible.
+fn div<MOD: SolinasParams<LIMBS>, const LIMBS: usize>(
+    lhs: &SolinasResidue<MOD, LIMBS>,
+    rhs: &SolinasResidue<MOD, LIMBS>,
+) -> CtOption<SolinasResidue<MOD, LIMBS>> {
+    let inverse = InvResidue::inv(*rhs);
+    let quotient = SolinasResidue::mul(lhs, &inverse.unwrap_or(SolinasResidue::ZERO));
+    CtOption::new(quotient, inverse.is_some())
+}
+
+impl_residue_binary_op!(
+    [MOD: SolinasParams<LIMBS>, const LIMBS: usize] SolinasResidue<MOD, LIMBS>,
+    CtOption<SolinasResidue<MOD, LIMBS>>,
+    Div,
+    div,
+    div
+);
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> Sum for SolinasResidue<MOD, LIMBS> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl<'a, MOD: SolinasParams<LIMBS>, const LIMBS: usize> Sum<&'a Self>
+    for SolinasResidue<MOD, LIMBS>
+{
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> Product for SolinasResidue<MOD, LIMBS> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+
+impl<'a, MOD: SolinasParams<LIMBS>, const LIMBS: usize> Product<&'a Self>
+    for SolinasResidue<MOD, LIMBS>
+{
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> ConstantTimeEq for SolinasResidue<MOD, LIMBS> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.value.ct_eq(&other.value)
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> ConditionallySelectable
+    for SolinasResidue<MOD, LIMBS>
+{
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            value: UInt::conditional_select(&a.value, &b.value, choice),
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> zeroize::Zeroize
+    for SolinasResidue<MOD, LIMBS>
+{
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> Neg for SolinasResidue<MOD, LIMBS> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        SolinasResidue::neg(&self)
+    }
+}
+
+impl<MOD: SolinasParams<LIMBS>, const LIMBS: usize> Neg for &SolinasResidue<MOD, LIMBS> {
+    type Output = SolinasResidue<MOD, LIMBS>;
+
+    fn neg(self) -> Self::Output {
+        SolinasResidue::neg(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::{RngCore, SeedableRng};
+    use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+    use super::{NistP224, NistP256, NistP384, SolinasResidue};
+    use crate::{
+        const_residue, impl_modulus,
+        modular::{InvResidue, SqrtResidue},
+        JacobiSymbol, U256, U384,
+    };
+
+    impl_modulus!(
+        P256Modulus,
+        U256,
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+    );
+
+    type Fe = SolinasResidue<NistP256, { U256::LIMBS }>;
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn matches_montgomery_residue() {
+        let x =
+            U256::from_be_hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+        let y =
+            U256::from_be_hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");
+        let exponent = U256::from(65537u64);
+
+        let (x_mont, y_mont) = (
+            const_residue!(x, P256Modulus),
+            const_residue!(y, P256Modulus),
+        );
+        let (x_sol, y_sol) = (Fe::new(x), Fe::new(y));
+
+        assert_eq!((x_sol * y_sol).retrieve(), (&x_mont * &y_mont).retrieve());
+        assert_eq!((x_sol - y_sol).retrieve(), (x_mont - y_mont).retrieve());
+        assert_eq!(
+            x_sol.pow_specific(&exponent, 17).retrieve(),
+            x_mont.pow_specific(&exponent, 17).retrieve()
+        );
+    }
+
+    #[test]
+    fn curve_equation() {
+        // The P-384 generator satisfies y^2 = x^3 - 3x + b
+        type Fe = SolinasResidue<NistP384, { U384::LIMBS }>;
+        let x = Fe::new(U384::from_be_hex("aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7"));
+        let y = Fe::new(U384::from_be_hex("3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f"));
+        let b = Fe::new(U384::from_be_hex("b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef"));
+        let three = Fe::new(U384::from(3u64));
+
+        assert_eq!(y.square(), x.square() * x - three * x + b);
+    }
+
+    #[test]
+    fn inversion() {
+        let x = Fe::new(U256::from(105u64));
+        assert_eq!(x * x.inv(), Fe::ONE);
+        assert_eq!(InvResidue::inv(x).unwrap(), x.inv());
+        assert!(bool::from(InvResidue::inv(Fe::ZERO).is_none()));
+    }
+
+    #[test]
+    fn random_matches_montgomery_residue() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        let mut random = || {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes);
+            U256::from_be_slice(&bytes)
+        };
+
+        for _ in 0..100 {
+            let (x, y, exponent) = (random(), random(), random());
+
+            let (x_mont, y_mont) = (
+                const_residue!(x, P256Modulus),
+                const_residue!(y, P256Modulus),
+            );
+            let (x_sol, y_sol) = (Fe::new(x), Fe::new(y));
+            assert_eq!(x_sol.retrieve(), x_mont.retrieve());
+
+            assert_eq!((x_sol * y_sol).retrieve(), (x_mont * y_mont).retrieve());
+            assert_eq!(x_sol.square().retrieve(), x_mont.square().retrieve());
+            assert_eq!(
+                x_sol.pow(&exponent).retrieve(),
+                x_mont.pow(&exponent).retrieve()
+            );
+            assert_eq!(
+                x_sol.pow_vartime(&exponent).retrieve(),
+                x_mont.pow(&exponent).retrieve()
+            );
+            assert_eq!(x_sol.inv().retrieve(), x_mont.inv().retrieve());
+        }
+    }
+
+    #[test]
+    fn const_arithmetic() {
+        const X: Fe = Fe::new(U256::from_u64(105));
+        const PRODUCT: Fe = X.mul(&X).square();
+        assert_eq!(PRODUCT, X.pow(&U256::from(4u64)));
+        assert_eq!(
+            X.pow_vartime(&U256::from(65537u64)),
+            X.pow(&U256::from(65537u64))
+        );
+        assert_eq!(X.pow_vartime(&U256::ZERO), Fe::ONE);
+    }
+
+    #[test]
+    fn sqrt_and_legendre() {
+        let x = Fe::new(U256::from(105u64));
+        let square = x.square();
+        assert_eq!(square.legendre(), JacobiSymbol::ONE);
+        assert_eq!(square.sqrt().unwrap().square(), square);
+        assert_eq!(Fe::ZERO.legendre(), JacobiSymbol::ZERO);
+
+        // -1 is a non-residue since the modulus is 3 mod 4
+        assert_eq!((-Fe::ONE).legendre(), JacobiSymbol::MINUS_ONE);
+        assert!(bool::from((-Fe::ONE).sqrt().is_none()));
+
+        // P-224 is 1 mod 2^96, which needs the Tonelli-Shanks algorithm
+        type Fe224 = SolinasResidue<NistP224, { U256::LIMBS }>;
+        let y = Fe224::new(U256::from(105u64)).square();
+        assert_eq!(y.sqrt().unwrap().square(), y);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn traits() {
+        let x = Fe::new(U256::from(105u64));
+        let y = Fe::new(U256::from(42u64));
+        assert!(bool::from(x.ct_eq(&x)));
+        assert!(!bool::from(x.ct_eq(&y)));
+        assert_eq!(Fe::conditional_select(&x, &y, Choice::from(0)), x);
+        assert_eq!(Fe::conditional_select(&x, &y, Choice::from(1)), y);
+
+        assert_eq!((&(x * y) / &y).unwrap(), x);
+        assert!(bool::from((x / Fe::ZERO).is_none()));
+        assert_eq!([x, y].iter().sum::<Fe>(), x + y);
+        assert_eq!([x, y].into_iter().product::<Fe>(), x * y);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::{Limb, UInt, Word, U192, U256, U384, U576};
+
+use super::{SolinasParams, SolinasReduction};
+
+/// The NIST P-192 prime `2^192 - 2^64 - 1`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NistP192 {}
+
+/// The NIST P-224 prime `2^224 - 2^96 + 1`.
+///
+/// It is represented with as many limbs as a [`U256`], since 224 bits are not a whole number of 64-bit limbs.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NistP224 {}
+
+/// The NIST P-256 prime `2^256 - 2^224 + 2^192 + 2^96 - 1`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NistP256 {}
+
+/// The NIST P-384 prime `2^384 - 2^128 - 2^96 + 2^32 - 1`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NistP384 {}
+
+/// The NIST P-521 prime `2^521 - 1`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NistP521 {}
+
+impl SolinasParams<{ U192::LIMBS }> for NistP192 {
+    const MODULUS: U192 = U192::from_be_hex("fffffffffffffffffffffffffffffffeffffffffffffffff");
+    const REDUCTION: SolinasReduction = SolinasReduction::NistP192;
+}
+
+impl SolinasParams<{ U256::LIMBS }> for NistP224 {
+    const MODULUS: U256 =
+        U256::from_be_hex("00000000ffffffffffffffffffffffffffffffff000000000000000000000001");
+    const REDUCTION: SolinasReduction = SolinasReduction::NistP224;
+}
+
+impl SolinasParams<{ U256::LIMBS }> for NistP256 {
+    const MODULUS: U256 =
+        U256::from_be_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+    const REDUCTION: SolinasReduction = SolinasReduction::NistP256;
+}
+
+impl SolinasParams<{ U384::LIMBS }> for NistP384 {
+    const MODULUS: U384 = U384::from_be_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff");
+    const REDUCTION: SolinasReduction = SolinasReduction::NistP384;
+}
+
+impl SolinasParams<{ U576::LIMBS }> for NistP521 {
+    const MODULUS: U576 = U576::ONE.shl_vartime(521).wrapping_sub(&U576::ONE);
+    const REDUCTION: SolinasReduction = SolinasReduction::Mersenne(521);
+}
+
+// The reductions below are the fast reductions from FIPS 186-4, Appendix D.2, on the 32-bit words `c[i]` of the input, least significant first.
+// Every word of the result is summed without carries, which are only propagated at the end.
+
+/// Reduces modulo P-192 as `T + S1 + S2 + S3`, returning a value less than twice the modulus.
+pub(super) const fn reduce_p192<const LIMBS: usize>(
+    lower_upper: &(UInt<LIMBS>, UInt<LIMBS>),
+) -> UInt<LIMBS> {
+    let c: [i64; 12] = words(lower_upper);
+    let sums = [
+        c[0] + c[6] + c[10],
+        c[1] + c[7] + c[11],
+        c[2] + c[6] + c[8] + c[10],
+        c[3] + c[7] + c[9] + c[11],
+        c[4] + c[8] + c[10],
+        c[5] + c[9] + c[11],
+    ];
+    // 2^192 = 2^64 + 1 mod p
+    from_words(fold_carries(sums, [1, 0, 1, 0, 0, 0]))
+}
+
+/// Reduces modulo P-224 as `T + S1 + S2 - D1 - D2`, returning a value less than twice the modulus.
+pub(super) const fn reduce_p224<const LIMBS: usize>(
+    lower_upper: &(UInt<LIMBS>, UInt<LIMBS>),
+) -> UInt<LIMBS> {
+    let c: [i64; 14] = words(lower_upper);
+    let sums = [
+        c[0] - c[7] - c[11],
+        c[1] - c[8] - c[12],
+        c[2] - c[9] - c[13],
+        c[3] + c[7] - c[10] + c[11],
+        c[4] + c[8] - c[11] + c[12],
+        c[5] + c[9] - c[12] + c[13],
+        c[6] + c[10] - c[13],
+    ];
+    // 2^224 = 2^96 - 1 mod p
+    from_words(fold_carries(sums, [-1, 0, 0, 1, 0, 0, 0]))
+}
+
+/// Reduces modulo P-256 as `T + 2 S1 + 2 S2 + S3 + S4 - D1 - D2 - D3 - D4`, returning a value less than twice the modulus.
+pub(super) const fn reduce_p256<const LIMBS: usize>(
+    lower_upper: &(UInt<LIMBS>, UInt<LIMBS>),
+) -> UInt<LIMBS> {
+    let c: [i64; 16] = words(lower_upper);
+    let sums = [
+        c[0] + c[8] + c[9] - c[11] - c[12] - c[13] - c[14],
+        c[1] + c[9] + c[10] - c[12] - c[13] - c[14] - c[15],
+        c[2] + c[10] + c[11] - c[13] - c[14] - c[15],
+        c[3] - c[8] - c[9] + 2 * c[11] + 2 * c[12] + c[13] - c[15],
+        c[4] - c[9] - c[10] + 2 * c[12] + 2 * c[13] + c[14],
+        c[5] - c[10] - c[11] + 2 * c[13] + 2 * c[14] + c[15],
+        c[6] - c[8] - c[9] + c[13] + 3 * c[14] + 2 * c[15],
+        c[7] + c[8] - c[10] - c[11] - c[12] - c[13] + 3 * c[15],
+    ];
+    // 2^256 = 2^224 - 2^192 - 2^96 + 1 mod p
+    from_words(fold_carries(sums, [1, 0, 0, -1, 0, 0, -1, 1]))
+}
+
+/// Reduces modulo P-384 as `T + 2 S1 + S2 + S3 + S4 + S5 + S6 - D1 - D2 - D3`, returning a value less than twice the modulus.
+pub(super) const fn reduce_p384<const LIMBS: usize>(
+    lower_upper: &(UInt<LIMBS>, UInt<LIMBS>),
+) -> UInt<LIMBS> {
+    let c: [i64; 24] = words(lower_upper);
+    let sums = [
+        c[0] + c[12] + c[20] + c[21] - c[23],
+        c[1] - c[12] + c[13] - c[20] + c[22] + c[23],
+        c[2] - c[13] + c[14] - c[21] + c[23],
+        c[3] + c[12] - c[14] + c[15] + c[20] + c[21] - c[22] - c[23],
+        c[4] + c[12] + c[13] - c[15] + c[16] + c[20] + 2 * c[21] + c[22] - 2 * c[23],
+        c[5] + c[13] + c[14] - c[16] + c[17] + c[21] + 2 * c[22] + c[23],
+        c[6] + c[14] + c[15] - c[17] + c[18] + c[22] + 2 * c[23],
+        c[7] + c[15] + c[16] - c[18] + c[19] + c[23],
+        c[8] + c[16] + c[17] - c[19] + c[20],
+        c[9] + c[17] + c[18] - c[20] + c[21],
+        c[10] + c[18] + c[19] - c[21] + c[22],
+        c[11] + c[19] + c[20] - c[22] + c[23],
+    ];
+    // 2^384 = 2^128 + 2^96 - 2^32 + 1 mod p
+    from_words(fold_carries(sums, [1, -1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0]))
+}
+
+/// Returns the lowest `N` 32-bit words of a wide integer, least significant first.
+const fn words<const LIMBS: usize, const N: usize>(
+    lower_upper: &(UInt<LIMBS>, UInt<LIMBS>),
+) -> [i64; N] {
+    let mut words = [0; N];
+    let mut i = 0;
+    while i < N {
+        let bit = i * 32;
+        let half = if bit < LIMBS * Limb::BIT_SIZE {
+            &lower_upper.0
+        } else {
+            &lower_upper.1
+        };
+        let bit = bit % (LIMBS * Limb::BIT_SIZE);
+        words[i] = (half.limbs[bit / Limb::BIT_SIZE].0 >> (bit % Limb::BIT_SIZE)) as u32 as i64;
+        i += 1;
+    }
+    words
+}
+
+/// Propagates the carries between the word sums `acc`. The (signed) carry out of the top word is folded back in as `carry * fold`, where `fold` holds the words of `2^(32 N)` modulo the prime.
+///
+/// For the NIST primes, two folds bring the carry to zero, leaving a value less than `2^(32 N)`, and thus less than twice the modulus.
+const fn fold_carries<const N: usize>(acc: [i64; N], fold: [i64; N]) -> [i64; N] {
+    let (mut acc, mut carry) = propagate_carries(acc);
+    let mut round = 0;
+    while round < 2 {
+        let mut j = 0;
+        while j < N {
+            acc[j] += carry * fold[j];
+            j += 1;
+        }
+        (acc, carry) = propagate_carries(acc);
+        round += 1;
+    }
+    debug_assert!(carry == 0);
+    acc
+}
+
+/// Normalizes the word sums `acc` into 32-bit words, returning them with the carry out of the top one.
+const fn propagate_carries<const N: usize>(mut acc: [i64; N]) -> ([i64; N], i64) {
+    let mut carry = 0;
+    let mut j = 0;
+    while j < N {
+        let value = acc[j] + carry;
+        acc[j] = value & 0xffff_ffff;
+        carry = value >> 32;
+        j += 1;
+    }
+    (acc, carry)
+}
+
+/// Assembles 32-bit words, least significant first, into an integer.
+const fn from_words<const LIMBS: usize, const N: usize>(words: [i64; N]) -> UInt<LIMBS> {
+    let mut limbs = [Limb::ZERO; LIMBS];
+    let mut i = 0;
+    while i < N {
+        let bit = i * 32;
+        limbs[bit / Limb::BIT_SIZE].0 |= (words[i] as Word) << (bit % Limb::BIT_SIZE);
+        i += 1;
+    }
+    UInt::new(limbs)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::{RngCore, SeedableRng};
+
+    use super::{NistP192, NistP224, NistP256, NistP384, NistP521};
+    use crate::{
+        modular::solinas_mod::{reduce_wide, SolinasParams},
+        Limb, UInt, Word, U192, U256, U384, U576,
+    };
+
+    /// Returns a random integer less than the modulus, which is either uniform or close to the modulus, where most of the carries of a reduction occur.
+    fn random_element<P: SolinasParams<LIMBS>, const LIMBS: usize>(
+        rng: &mut impl RngCore,
+    ) -> UInt<LIMBS> {
+        let mut x = UInt::ZERO;
+        for limb in x.limbs.iter_mut() {
+            let mut bytes = [0u8; Limb::BYTE_SIZE];
+            rng.fill_bytes(&mut bytes);
+            *limb = Limb(Word::from_le_bytes(bytes));
+        }
+        let x = x.ct_reduce(&P::MODULUS).0;
+        if rng.next_u32() % 2 == 0 {
+            x
+        } else {
+            let distance = x.shr_vartime(P::MODULUS.bits_vartime() / 2);
+            P::MODULUS.wrapping_sub(&UInt::ONE).wrapping_sub(&distance)
+        }
+    }
+
+    fn check_reduction<P: SolinasParams<LIMBS>, const LIMBS: usize>() {
+        let minus_one = P::MODULUS.wrapping_sub(&UInt::ONE);
+        let half = P::MODULUS.shr_vartime(1);
+        let mut pattern = UInt::ZERO;
+        for (i, limb) in pattern.limbs.iter_mut().enumerate() {
+            *limb = Limb((Word::MAX / 7).wrapping_mul(i as Word + 3));
+        }
+        let pattern = pattern.ct_reduce(&P::MODULUS).0;
+        let values = [UInt::ZERO, UInt::ONE, minus_one, half, pattern];
+
+        for x in values {
+            for y in values {
+                let product = x.mul_wide(&y);
+                let expected = UInt::ct_reduce_wide(product, &P::MODULUS).0;
+                assert_eq!(reduce_wide::<P, LIMBS>(product), expected);
+            }
+        }
+
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        for _ in 0..1000 {
+            let x = random_element::<P, LIMBS>(&mut rng);
+            let y = random_element::<P, LIMBS>(&mut rng);
+            let product = x.mul_wide(&y);
+            let expected = UInt::ct_reduce_wide(product, &P::MODULUS).0;
+            assert_eq!(reduce_wide::<P, LIMBS>(product), expected);
+        }
+    }
+
+    #[test]
+    fn moduli() {
+        assert_eq!(NistP224::MODULUS.bits_vartime(), 224);
+        assert_eq!(NistP521::MODULUS.shr_vartime(512), U576::from(0x1ffu64));
+    }
+
+    #[test]
+    fn reductions() {
+        check_reduction::<NistP192, { U192::LIMBS }>();
+        check_reduction::<NistP224, { U256::LIMBS }>();
+        check_reduction::<NistP256, { U256::LIMBS }>();
+        check_reduction::<NistP384, { U384::LIMBS }>();
+        check_reduction::<NistP521, { U576::LIMBS }>();
+    }
+}
@@ -0,0 +1,463 @@
+//! For `BITS = LIMBS * Word::BITS`, the modulus has the form `MAX + 1 - c` assumed by [`UInt::add_mod_special`], [`UInt::sub_mod_special`], [`UInt::neg_mod_special`] and [`UInt::mul_mod_special`], and the residue arithmetic is built on them.
+//! Those functions cannot handle the other supported sizes, such as `2^255 - 19` in four 64-bit limbs, as they rely on the overflow out of the top limb to wrap around by `c`; there the generic modular addition and subtraction are used, and products are reduced by folding at bit `BITS` instead.
+//! A single-limb `mul_mod_special` falls back to a variable-time division, so it is not used either.
+
+use core::ops::Neg;
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::{Limb, UInt, WideWord, Word};
+
+use super::{
+    AddResidue, GenericResidue, InvResidue, MulResidue, NegResidue, PowResidue, SubResidue,
+};
+
+/// A residue mod the pseudo-Mersenne prime `p = 2^BITS - C`, such as secp256k1's base field prime `2^256 - 0x1000003d1` or Curve25519's `2^255 - 19`.
+///
+/// The residue is stored as a plain integer in `[0, p)`, and reductions use the special form of the modulus, so no conversion to and from Montgomery form is needed.
+/// `C` must be odd and less than `2^(BITS / 2)`, and `BITS` must be more than half of `LIMBS * Word::BITS`; this is checked at compile time.
+#[derive(Debug, Clone, Copy)]
+pub struct PseudoMersenneResidue<const LIMBS: usize, const BITS: usize, const C: Word> {
+    value: UInt<LIMBS>,
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> PseudoMersenneResidue<LIMBS, BITS, C> {
+    /// The modulus `2^BITS - C`.
+    pub const MODULUS: UInt<LIMBS> = {
+        assert!(C % 2 == 1, "C must be odd");
+        assert!(
+            2 * (Word::BITS - C.leading_zeros()) as usize <= BITS,
+            "C must be less than 2^(BITS / 2)"
+        );
+        assert!(
+            2 * BITS > LIMBS * Limb::BIT_SIZE && BITS <= LIMBS * Limb::BIT_SIZE,
+            "BITS must be more than half of the size of the residue"
+        );
+        UInt::ONE
+            .shl_vartime(BITS)
+            .wrapping_sub(&UInt::from_word(C))
+    };
+
+    /// Whether the modulus is `2^(LIMBS * Word::BITS) - C`, as required by the `UInt::*_mod_special` functions.
+    const FULL_WIDTH: bool = BITS == LIMBS * Limb::BIT_SIZE;
+
+    /// `2^BITS - 1`, which selects the bits below the fold.
+    const MASK: UInt<LIMBS> = UInt::ONE.shl_vartime(BITS).wrapping_sub(&UInt::ONE);
+
+    /// The representation of 0 mod `p`.
+    pub const ZERO: Self = Self { value: UInt::ZERO };
+
+    /// The representation of 1 mod `p`.
+    pub const ONE: Self = Self { value: UInt::ONE };
+
+    /// Instantiates a new `PseudoMersenneResidue` that represents this `integer` mod `p`.
+    pub const fn new(integer: UInt<LIMBS>) -> Self {
+        Self {
+            value: Self::reduce_wide(integer, UInt::ZERO),
+        }
+    }
+
+    /// Retrieves the integer currently encoded in this residue, guaranteed to be reduced.
+    pub const fn retrieve(&self) -> UInt<LIMBS> {
+        self.value
+    }
+
+    /// Adds `rhs`.
+    pub const fn add(&self, rhs: &Self) -> Self {
+        let value = if Self::FULL_WIDTH {
+            self.value.add_mod_special(&rhs.value, Limb(C))
+        } else {
+            self.value.add_mod(&rhs.value, &Self::MODULUS)
+        };
+        Self { value }
+    }
+
+    /// Subtracts `rhs`.
+    pub const fn sub(&self, rhs: &Self) -> Self {
+        let value = if Self::FULL_WIDTH {
+            self.value.sub_mod_special(&rhs.value, Limb(C))
+        } else {
+            self.value.sub_mod(&rhs.value, &Self::MODULUS)
+        };
+        Self { value }
+    }
+
+    /// Negates the residue.
+    pub const fn neg(&self) -> Self {
+        let value = if Self::FULL_WIDTH {
+            self.value.neg_mod_special(Limb(C))
+        } else {
+            self.value.neg_mod(&Self::MODULUS)
+        };
+        Self { value }
+    }
+
+    /// Multiplies by `rhs`.
+    pub const fn mul(&self, rhs: &Self) -> Self {
+        if Self::FULL_WIDTH && LIMBS > 1 {
+            return Self {
+                value: self.value.mul_mod_special(&rhs.value, Limb(C)),
+            };
+        }
+
+        let (lo, hi) = self.value.mul_wide(&rhs.value);
+        Self {
+            value: Self::reduce_wide(lo, hi),
+        }
+    }
+
+    /// Computes the (reduced) square of a residue.
+    pub const fn square(&self) -> Self {
+        Self::mul(self, self)
+    }
+
+    /// Performs modular exponentiation, taking `exponent_bits` bits of the exponent into account. Note that this value is leaked in the time pattern.
+    ///
+    /// A multiplication is performed for every bit of the exponent and its result selected in constant time, so the exponent itself is not leaked.
+    pub const fn pow_specific<const RHS_LIMBS: usize>(
+        &self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self {
+        let mut z = Self::ONE;
+        let mut i = exponent_bits;
+        while i > 0 {
+            i -= 1;
+            z = Self::square(&z);
+            let product = Self::mul(&z, self);
+            let bit = exponent.bit_vartime(i).wrapping_neg();
+            z.value = UInt::ct_select(z.value, product.value, bit);
+        }
+        z
+    }
+
+    /// Computes the residue `self^-1` representing the multiplicative inverse of `self`. I.e. `self * self^-1 = 1`. Panics if `self` was not invertible.
+    pub const fn inv(self) -> Self {
+        let (value, error) = self.value.inv_odd_mod(Self::MODULUS);
+        assert!(error == Word::MAX);
+        Self { value }
+    }
+
+    /// Reduces `hi * 2^(LIMBS * Word::BITS) + lo`, which must be less than `2^(2 * BITS)`.
+    ///
+    /// Since `2^BITS = C mod p`, the bits from `BITS` upwards are folded down by multiplying them by `C`, twice.
+    /// Afterwards the value is less than `p + C^2 + C < 2p`, so a single conditional subtraction of `p` suffices.
+    const fn reduce_wide(lo: UInt<LIMBS>, hi: UInt<LIMBS>) -> UInt<LIMBS> {
+        let shift = LIMBS * Limb::BIT_SIZE - BITS;
+
+        // First fold: `(x mod 2^BITS) + C * (x >> BITS)`, which is less than `2^BITS * (C + 1)`
+        let high = lo.shr_vartime(BITS).bitor(&hi.shl_vartime(shift));
+        let mut folded = lo.bitand(&Self::MASK);
+        let mut carry = Limb::ZERO;
+        let mut i = 0;
+        while i < LIMBS {
+            let (limb, c) = folded.limbs[i].mac(high.limbs[i], Limb(C), carry);
+            folded.limbs[i] = limb;
+            carry = c;
+            i += 1;
+        }
+
+        // Second fold: the bits from `BITS` upwards are now at most `C`
+        let high = folded
+            .shr_vartime(BITS)
+            .bitor(&UInt::from_word(carry.0).shl_vartime(shift))
+            .limbs[0];
+        let wide_product = high.0 as WideWord * C as WideWord;
+        let mut product = UInt::from_word(wide_product as Word);
+        // The product is less than `2^BITS`, so for a single limb it fits in the low word
+        if LIMBS > 1 {
+            product.limbs[1] = Limb((wide_product >> Limb::BIT_SIZE) as Word);
+        }
+        let (folded, carry) = folded.bitand(&Self::MASK).adc(&product, Limb::ZERO);
+
+        // Subtract `p` unless that underflows, taking the carry out of the top limb into account
+        let (reduced, borrow) = folded.sbb(&Self::MODULUS, Limb::ZERO);
+        let (_, borrow) = carry.sbb(Limb::ZERO, borrow);
+        UInt::ct_select(reduced, folded, borrow.0)
+    }
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> AddResidue
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    fn add(&self, rhs: &Self) -> Self {
+        PseudoMersenneResidue::add(self, rhs)
+    }
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> SubResidue
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    fn sub(&self, rhs: &Self) -> Self {
+        PseudoMersenneResidue::sub(self, rhs)
+    }
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> NegResidue
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    fn neg(&self) -> Self {
+        PseudoMersenneResidue::neg(self)
+    }
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> MulResidue
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    fn mul(&self, rhs: &Self) -> Self {
+        PseudoMersenneResidue::mul(self, rhs)
+    }
+
+    fn square(&self) -> Self {
+        PseudoMersenneResidue::square(self)
+    }
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> PowResidue<LIMBS>
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    fn pow_specific<const RHS_LIMBS: usize>(
+        self,
+        exponent: &UInt<RHS_LIMBS>,
+        exponent_bits: usize,
+    ) -> Self {
+        PseudoMersenneResidue::pow_specific(&self, exponent, exponent_bits)
+    }
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> InvResidue
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    fn inv(self) -> CtOption<Self> {
+        let (value, error) = self.value.inv_odd_mod(Self::MODULUS);
+        CtOption::new(Self { value }, Choice::from((error == Word::MAX) as u8))
+    }
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> GenericResidue<LIMBS>
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    fn retrieve(&self) -> UInt<LIMBS> {
+        PseudoMersenneResidue::retrieve(self)
+    }
+}
+
+impl_residue_binary_op!(
+    [const LIMBS: usize, const BITS: usize, const C: Word] PseudoMersenneResidue<LIMBS, BITS, C>,
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    PseudoMersenneResidue::add
+);
+
+impl_residue_binary_op!(
+    [const LIMBS: usize, const BITS: usize, const C: Word] PseudoMersenneResidue<LIMBS, BITS, C>,
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    PseudoMersenneResidue::sub
+);
+
+impl_residue_binary_op!(
+    [const LIMBS: usize, const BITS: usize, const C: Word] PseudoMersenneResidue<LIMBS, BITS, C>,
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    PseudoMersenneResidue::mul
+);
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> Neg
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        PseudoMersenneResidue::neg(&self)
+    }
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> Neg
+    for &PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    type Output = PseudoMersenneResidue<LIMBS, BITS, C>;
+
+    fn neg(self) -> Self::Output {
+        PseudoMersenneResidue::neg(self)
+    }
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> ConstantTimeEq
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.value.ct_eq(&other.value)
+    }
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> PartialEq
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> Eq
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+}
+
+impl<const LIMBS: usize, const BITS: usize, const C: Word> ConditionallySelectable
+    for PseudoMersenneResidue<LIMBS, BITS, C>
+{
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            value: UInt::conditional_select(&a.value, &b.value, choice),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+    use super::PseudoMersenneResidue;
+    use crate::{modular::InvResidue, U256, U64};
+
+    // secp256k1's base field: p = 2^256 - 0x1000003d1
+    type Fe = PseudoMersenneResidue<{ U256::LIMBS }, 256, 0x1000003d1>;
+
+    // Curve25519's base field: p = 2^255 - 19
+    type Fe25519 = PseudoMersenneResidue<{ U256::LIMBS }, 255, 19>;
+
+    #[test]
+    fn modulus() {
+        assert_eq!(
+            Fe::MODULUS,
+            U256::from_be_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f")
+        );
+    }
+
+    #[test]
+    fn new_reduces() {
+        let x = Fe::new(U256::MAX);
+        assert_eq!(x.retrieve(), U256::from(0x1000003d0u64));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let x = Fe::new(U256::from_be_hex(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        ));
+        let y = Fe::new(U256::from_be_hex(
+            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+        ));
+
+        assert_eq!(
+            (x + y).retrieve(),
+            U256::from_be_hex("c1f940f620808011b3455e91dc9813afffb3b123d4537cf2f63a51eb1208ec50")
+        );
+        assert_eq!(
+            (x - y).retrieve(),
+            U256::from_be_hex("31838c07d338f746f7fb6699c076025e058448928748d4bfbdaab0cb1be742e0")
+        );
+        assert_eq!(
+            (y - x).retrieve(),
+            U256::from_be_hex("ce7c73f82cc708b9080499663f89fda1fa7bb76d78b72b4042554f33e418b94f")
+        );
+        assert_eq!(
+            (x * y).retrieve(),
+            U256::from_be_hex("fd3dc529c6eb60fb9d166034cf3c1a5a72324aa9dfd3428a56d7e1ce0179fd9b")
+        );
+        assert_eq!((-x + x).retrieve(), U256::ZERO);
+    }
+
+    #[test]
+    fn pow_and_inversion() {
+        let x = Fe::new(U256::from_be_hex(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        ));
+
+        // The curve equation y^2 = x^3 + 7 for the generator
+        let y = Fe::new(U256::from_be_hex(
+            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+        ));
+        assert_eq!(
+            x.pow_specific(&U256::from(3u64), 2) + Fe::new(U256::from(7u64)),
+            y.square()
+        );
+
+        let inv = x.inv();
+        assert_eq!(x * inv, Fe::ONE);
+        assert_eq!(InvResidue::inv(x).unwrap(), inv);
+        assert!(bool::from(InvResidue::inv(Fe::ZERO).is_none()));
+    }
+
+    #[test]
+    fn non_word_multiple_modulus() {
+        assert_eq!(
+            Fe25519::MODULUS,
+            U256::from_be_hex("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed")
+        );
+        assert_eq!(Fe25519::new(U256::MAX).retrieve(), U256::from(0x25u64));
+
+        // The x and y coordinates of the Ed25519 base point
+        let x = Fe25519::new(U256::from_be_hex(
+            "216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a",
+        ));
+        let y = Fe25519::new(U256::from_be_hex(
+            "6666666666666666666666666666666666666666666666666666666666666658",
+        ));
+
+        assert_eq!(
+            (x * y).retrieve(),
+            U256::from_be_hex("67875f0fd78b766566ea4e8e64abe37d20f09f80775152f56dde8ab3a5b7dda3")
+        );
+        assert_eq!(
+            (x + y).retrieve(),
+            U256::from_be_hex("07cf9d3a33d4ba65270b4898643d42c2cf932dc6fb8c0e192fbc93c6f58c3b85")
+        );
+        assert_eq!(
+            (x - y).retrieve(),
+            U256::from_be_hex("3b02d06d6707ed985a3e7bcb977075f602c660fa2ebf414c62efc6fa28bf6eaf")
+        );
+        assert_eq!((-x + x).retrieve(), U256::ZERO);
+        assert_eq!(
+            x.inv().retrieve(),
+            U256::from_be_hex("5272fb06866a6cd6a133e3762c993863943c29930c8453b46731350a14b947df")
+        );
+
+        // The largest product of reduced values
+        let minus_one = -Fe25519::ONE;
+        assert_eq!(minus_one.square(), Fe25519::ONE);
+        assert_eq!(
+            minus_one * Fe25519::new(U256::from(2u64)),
+            -Fe25519::new(U256::from(2u64))
+        );
+    }
+
+    #[test]
+    fn single_limb() {
+        // p = 2^64 - 59
+        type Fe64 = PseudoMersenneResidue<{ U64::LIMBS }, 64, 59>;
+        let x = Fe64::new(U64::from_u64(0xfedcba9876543210));
+        let y = Fe64::new(U64::from_u64(0x0123456789abcdef));
+        assert_eq!(x.retrieve(), U64::from_u64(0xfedcba9876543210));
+        assert_eq!((x * y).retrieve(), U64::from_u64(0x650b76b7e0002926));
+        assert_eq!((-x + x).retrieve(), U64::ZERO);
+        assert_eq!(x * x.inv(), Fe64::ONE);
+    }
+
+    #[test]
+    fn constant_time_traits() {
+        let x = Fe::new(U256::from(3u64));
+        let y = Fe::new(U256::from(5u64));
+        assert!(bool::from(x.ct_eq(&x)));
+        assert!(!bool::from(x.ct_eq(&y)));
+        assert_eq!(Fe::conditional_select(&x, &y, Choice::from(0)), x);
+        assert_eq!(Fe::conditional_select(&x, &y, Choice::from(1)), y);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{JacobiSymbol, Limb, UInt, Word};
+
+use super::{
+    mul::{mul_montgomery_form, square_montgomery_form},
+    pow::pow_montgomery_form,
+};
+
+/// The maximum number of candidates tried when searching for a quadratic non-residue.
+/// For a prime modulus the smallest non-residue is tiny, so this bound only matters if the modulus is not prime.
+const MAX_NON_RESIDUE_CANDIDATES: u64 = 1 << 16;
+
+/// Computes a square root of `x` (in Montgomery form) modulo the odd prime `modulus`.
+/// Returns `(root, Word::MAX)` if a square root exists, otherwise `(undefined, 0)`.
+///
+/// Depending on the modulus, either the `p = 3 mod 4` formula, Atkin's `p = 5 mod 8` formula
+/// or a constant-time variant of Tonelli-Shanks is used.
+/// The latter needs `modulus - 1 = odd_part * 2^two_adicity` with `odd_part` odd, and a primitive
+/// `2^two_adicity`-th root of unity in Montgomery form, which is ignored for the other moduli.
+/// Only properties of the modulus are leaked in the time pattern, not properties of `x`.
+pub(crate) const fn sqrt_montgomery_form<const LIMBS: usize>(
+    x: UInt<LIMBS>,
+    modulus: UInt<LIMBS>,
+    r: UInt<LIMBS>,
+    mod_neg_inv: Limb,
+    two_adicity: usize,
+    odd_part: &UInt<LIMBS>,
+    root_of_unity: &UInt<LIMBS>,
+) -> (UInt<LIMBS>, Word) {
+    let exponent_bits = LIMBS * Word::BITS as usize;
+
+    let root = if modulus.limbs[0].0 & 3 == 3 {
+        // p = 3 mod 4: x^((p+1)/4)
+        let exponent = modulus.shr_vartime(2).wrapping_add(&UInt::ONE);
+        pow_montgomery_form(x, &exponent, exponent_bits, modulus, r, mod_neg_inv)
+    } else if modulus.limbs[0].0 & 7 == 5 {
+        // p = 5 mod 8 (Atkin): t = (2x)^((p-5)/8), i = 2x t^2, root = x t (i - 1)
+        let exponent = modulus.shr_vartime(3);
+        let x2 = x.add_mod(&x, &modulus);
+        let t = pow_montgomery_form(x2, &exponent, exponent_bits, modulus, r, mod_neg_inv);
+        let i = mul_montgomery_form(
+            &x2,
+            &square_montgomery_form(&t, modulus, mod_neg_inv),
+            modulus,
+            mod_neg_inv,
+        );
+        let xt = mul_montgomery_form(&x, &t, modulus, mod_neg_inv);
+        mul_montgomery_form(&xt, &i.sub_mod(&r, &modulus), modulus, mod_neg_inv)
+    } else {
+        tonelli_shanks(
+            x,
+            two_adicity,
+            odd_part,
+            *root_of_unity,
+            modulus,
+            r,
+            mod_neg_inv,
+        )
+    };
+
+    let is_root = square_montgomery_form(&root, modulus, mod_neg_inv).ct_not_eq(&x) ^ Word::MAX;
+    (root, is_root)
+}
+
+/// Constant-time Tonelli-Shanks, following the algorithm in
+/// [RFC 9380, Appendix I.4](https://www.rfc-editor.org/rfc/rfc9380.html#appendix-I.4).
+/// The number of iterations only depends on the 2-adicity of `modulus - 1`.
+const fn tonelli_shanks<const LIMBS: usize>(
+    x: UInt<LIMBS>,
+    two_adicity: usize,
+    odd_part: &UInt<LIMBS>,
+    root_of_unity: UInt<LIMBS>,
+    modulus: UInt<LIMBS>,
+    r: UInt<LIMBS>,
+    mod_neg_inv: Limb,
+) -> UInt<LIMBS> {
+    let exponent_bits = LIMBS * Word::BITS as usize;
+
+    let mut z = pow_montgomery_form(
+        x,
+        &odd_part.shr_vartime(1),
+        exponent_bits,
+        modulus,
+        r,
+        mod_neg_inv,
+    );
+    let mut t = mul_montgomery_form(
+        &square_montgomery_form(&z, modulus, mod_neg_inv),
+        &x,
+        modulus,
+        mod_neg_inv,
+    );
+    z = mul_montgomery_form(&z, &x, modulus, mod_neg_inv);
+    let mut b = t;
+    let mut c = root_of_unity;
+
+    let mut i = two_adicity;
+    while i >= 2 {
+        let mut j = 1;
+        while j <= i - 2 {
+            b = square_montgomery_form(&b, modulus, mod_neg_inv);
+            j += 1;
+        }
+        let b_is_one = b.ct_not_eq(&r) ^ Word::MAX;
+        let zc = mul_montgomery_form(&z, &c, modulus, mod_neg_inv);
+        z = UInt::ct_select(zc, z, b_is_one);
+        c = square_montgomery_form(&c, modulus, mod_neg_inv);
+        let tc = mul_montgomery_form(&t, &c, modulus, mod_neg_inv);
+        t = UInt::ct_select(tc, t, b_is_one);
+        b = t;
+        i -= 1;
+    }
+
+    z
+}
+
+/// Returns the 2-adicity `s` of `modulus - 1` for an odd `modulus` greater than 1, i.e. the largest `s` such that `2^s` divides it.
+pub(crate) const fn two_adicity<const LIMBS: usize>(modulus: &UInt<LIMBS>) -> usize {
+    assert!(
+        modulus.ct_not_eq(&UInt::ONE) != 0,
+        "the modulus must be greater than 1"
+    );
+
+    let mut s = 1;
+    while modulus.bit_vartime(s) == 0 {
+        s += 1;
+    }
+    s
+}
+
+/// Searches for the smallest integer whose Jacobi symbol modulo the odd `modulus` is `-1`, which is the smallest quadratic non-residue if `modulus` is prime.
+/// Returns `None` if there is none, which is the case if `modulus` is a perfect square, or if there is none below `2^16`, which only happens if `modulus` is not prime.
+/// This is variable-time, but only depends on the (public) modulus.
+pub(crate) const fn find_non_residue<const LIMBS: usize>(
+    modulus: &UInt<LIMBS>,
+) -> Option<UInt<LIMBS>> {
+    let mut candidate = 2;
+    while candidate < MAX_NON_RESIDUE_CANDIDATES {
+        let integer = UInt::from_u64(candidate);
+        if integer.jacobi(modulus).to_i8() == JacobiSymbol::MINUS_ONE.to_i8() {
+            return Some(integer);
+        }
+
+        // The Jacobi symbol modulo a perfect square is never -1, so give up early for those
+        if candidate == 64 {
+            let root = modulus.sqrt();
+            if root.square_wide().0.ct_not_eq(modulus) == 0 {
+                return None;
+            }
+        }
+        candidate += 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_non_residue;
+    use crate::U256;
+
+    #[test]
+    fn non_residue() {
+        // The smallest non-residues modulo the BLS12-381 scalar field and P-256
+        let modulus =
+            U256::from_be_hex("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001");
+        assert_eq!(find_non_residue(&modulus), Some(U256::from(5u64)));
+        let modulus =
+            U256::from_be_hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+        assert_eq!(find_non_residue(&modulus), Some(U256::from(3u64)));
+
+        // There is none modulo a perfect square
+        assert_eq!(find_non_residue(&U256::from(441u64)), None);
+        assert_eq!(find_non_residue(&U256::ONE), None);
+    }
+}
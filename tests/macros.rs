@@ -0,0 +1,51 @@
+//! Invocations of the exported macros from outside of `crypto-bigint`
+
+use crypto_bigint::{const_residue, impl_modulus, U256};
+
+impl_modulus!(
+    Modulus,
+    U256,
+    "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+);
+
+#[test]
+fn impl_modulus() {
+    use crypto_bigint::modular::{constant_mod::ResidueParams, runtime_mod::DynResidueParams};
+
+    let params = DynResidueParams::new(Modulus::MODULUS);
+    assert_eq!(&Modulus::R, params.r());
+    assert_eq!(&Modulus::R2, params.r2());
+    assert_eq!(Modulus::MOD_NEG_INV, params.mod_neg_inv());
+
+    let x = U256::from(105u64);
+    let x_mod = const_residue!(x, Modulus);
+    assert_eq!(x_mod.retrieve(), x);
+    assert_eq!((x_mod * x_mod.inv()).retrieve(), U256::ONE);
+}
+
+#[cfg(feature = "ff")]
+mod prime_field {
+    use crypto_bigint::{
+        ff::{Field, PrimeField},
+        impl_prime_field, U256,
+    };
+
+    impl_prime_field!(
+        Scalar,
+        ScalarModulus,
+        U256,
+        "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+        7
+    );
+
+    #[test]
+    fn impl_prime_field() {
+        assert_eq!(Scalar::NUM_BITS, 255);
+        assert_eq!(Scalar::TWO_INV.double(), Scalar::ONE);
+
+        let x = Scalar::from(105u64);
+        assert_eq!(x * x.invert().unwrap(), Scalar::ONE);
+        assert_eq!(x.square().sqrt().unwrap().square(), x.square());
+        assert_eq!(Scalar::from_repr(x.to_repr()).unwrap(), x);
+    }
+}